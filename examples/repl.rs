@@ -3,7 +3,7 @@ extern crate mini_v8;
 extern crate rustyline;
 
 use ansi_term::Colour::{Green, Red, Fixed};
-use mini_v8::{MiniV8, Value, Error as MV8Error, Script, ScriptOrigin};
+use mini_v8::{ConsoleLevel, MiniV8, Value, Values, Error as MV8Error, ReplContext};
 use rustyline::{Editor, error::ReadlineError};
 use std::time::SystemTime;
 
@@ -11,10 +11,12 @@ fn main() {
     println!("Type \\h for help.\n");
 
     let mv8 = MiniV8::new();
+    mv8.set_console(print_console).unwrap();
+    let mut repl = ReplContext::new("repl");
     let mut rl = Editor::<()>::new();
 
     loop {
-        match rl.readline(&"# ") {
+        match rl.readline("# ") {
             Ok(ref line) if line.starts_with("\\") => {
                 let code = &line[1..line.len()];
                 match code {
@@ -27,17 +29,13 @@ fn main() {
             },
             Ok(line) => {
                 let before = SystemTime::now();
-                let result: Result<Value, MV8Error> = mv8.eval(Script {
-                    source: line.clone(),
-                    origin: Some(ScriptOrigin { name: "repl".to_owned(), ..Default::default() }),
-                    ..Default::default()
-                });
+                let result: Result<Value, MV8Error> = repl.eval(&mv8, line.clone());
                 let elapsed = SystemTime::now().duration_since(before).unwrap();
                 match result {
                     Ok(value) => print_value(value),
                     Err(error) => print_error(error.to_value(&mv8)),
                 }
-                println!("{}", Fixed(245).paint(&format!("Evaluated in {:?}", elapsed)));
+                println!("{}", Fixed(245).paint(format!("Evaluated in {:?}", elapsed)));
 
                 rl.add_history_entry(line);
             },
@@ -57,6 +55,14 @@ fn print_help() {
     println!("      \\h for this dialog");
 }
 
+fn print_console(level: ConsoleLevel, args: Values) {
+    let args: Vec<String> = args.iter().map(|arg| format!("{:?}", arg)).collect();
+    match level {
+        ConsoleLevel::Warn | ConsoleLevel::Error => eprintln!("{}", args.join(" ")),
+        ConsoleLevel::Log | ConsoleLevel::Info => println!("{}", args.join(" ")),
+    }
+}
+
 fn print_value(value: Value) {
     println!("{} {:?}", Green.paint("=>"), value);
 }
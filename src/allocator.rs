@@ -0,0 +1,104 @@
+use std::alloc::{self, Layout};
+use std::ffi::c_void;
+use std::ptr;
+
+/// Accounts for the memory backing a `MiniV8`'s `ArrayBuffer`s (and typed arrays), e.g. to cap the
+/// total size of the buffers that untrusted code can allocate. See `MiniV8::new_with_allocator`.
+///
+/// V8 calls `allocate` before allocating a buffer's memory, and `free` after freeing it. Both may
+/// be called from threads other than the one using the `MiniV8`.
+pub trait BufferAllocator: Send + Sync + 'static {
+    /// Requests `len` bytes of memory for a buffer. Return `false` to refuse the allocation, in
+    /// which case the JavaScript code creating the buffer throws a `RangeError`.
+    fn allocate(&self, len: usize) -> bool;
+
+    /// Notifies that `len` bytes of memory, previously granted by `allocate`, have been freed.
+    fn free(&self, len: usize);
+}
+
+// Creates a V8 array buffer allocator that allocates memory with Rust's global allocator, after
+// checking with the given `BufferAllocator`.
+pub(crate) fn create_allocator(
+    allocator: Box<dyn BufferAllocator>,
+) -> v8::UniqueRef<v8::Allocator> {
+    let handle = Box::into_raw(Box::new(allocator));
+    unsafe { v8::new_rust_allocator(handle, &VTABLE) }
+}
+
+type Handle = Box<dyn BufferAllocator>;
+
+static VTABLE: v8::RustAllocatorVtable<Handle> = v8::RustAllocatorVtable {
+    allocate,
+    allocate_uninitialized,
+    free,
+    reallocate,
+    drop,
+};
+
+// Buffers are aligned generously, as V8 may store any kind of value in them:
+const ALIGN: usize = 16;
+
+// Zero-sized allocations are not allowed by Rust's global allocator, so at least a byte is always
+// allocated:
+fn layout(len: usize) -> Layout {
+    Layout::from_size_align(len.max(1), ALIGN).unwrap()
+}
+
+unsafe extern "C" fn allocate(handle: &Handle, len: usize) -> *mut c_void {
+    if !handle.allocate(len) {
+        return ptr::null_mut();
+    }
+    let data = alloc::alloc_zeroed(layout(len));
+    if data.is_null() {
+        handle.free(len);
+    }
+    data as *mut c_void
+}
+
+unsafe extern "C" fn allocate_uninitialized(handle: &Handle, len: usize) -> *mut c_void {
+    if !handle.allocate(len) {
+        return ptr::null_mut();
+    }
+    let data = alloc::alloc(layout(len));
+    if data.is_null() {
+        handle.free(len);
+    }
+    data as *mut c_void
+}
+
+unsafe extern "C" fn free(handle: &Handle, data: *mut c_void, len: usize) {
+    if !data.is_null() {
+        alloc::dealloc(data as *mut u8, layout(len));
+        handle.free(len);
+    }
+}
+
+unsafe extern "C" fn reallocate(
+    handle: &Handle,
+    data: *mut c_void,
+    old_len: usize,
+    new_len: usize,
+) -> *mut c_void {
+    // Only the change in size is accounted for, so that growing a buffer isn't refused for briefly
+    // counting both its old and new memory:
+    if new_len > old_len && !handle.allocate(new_len - old_len) {
+        return ptr::null_mut();
+    }
+    let new_data = alloc::realloc(data as *mut u8, layout(old_len), new_len.max(1));
+    if new_data.is_null() {
+        if new_len > old_len {
+            handle.free(new_len - old_len);
+        }
+        return ptr::null_mut();
+    }
+    if new_len > old_len {
+        ptr::write_bytes(new_data.add(old_len), 0, new_len - old_len);
+    } else {
+        handle.free(old_len - new_len);
+    }
+    new_data as *mut c_void
+}
+
+unsafe extern "C" fn drop(handle: *const Handle) {
+    std::mem::drop(Box::from_raw(handle as *mut Handle));
+}
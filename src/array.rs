@@ -1,4 +1,5 @@
 use crate::*;
+use crate::value::debug_nested;
 use std::fmt;
 use std::marker::PhantomData;
 
@@ -20,6 +21,27 @@ impl Array {
         })
     }
 
+    /// Returns `true` if this and `other` refer to the same JavaScript array, as `===` would.
+    /// Separate arrays are never identical, even if their contents are; see `deep_equals` for
+    /// comparing contents.
+    pub fn ptr_eq(&self, other: &Array) -> bool {
+        self.mv8.scope(|scope| {
+            let value = v8::Local::new(scope, self.handle.clone());
+            let other = v8::Local::new(scope, other.handle.clone());
+            value.strict_equals(other.into())
+        })
+    }
+
+    /// Returns `true` if this array is structurally equal to `other`, i.e. both have the same
+    /// length and deeply equal elements. See `Value::deep_equals` for the exact semantics. This is
+    /// also how `PartialEq` compares arrays, so `==` may run JavaScript (e.g. getters).
+    ///
+    /// Returns an error if reading an element throws, e.g. from a getter.
+    pub fn deep_equals(&self, other: &Array) -> Result<bool> {
+        let value = Value::Array(self.clone());
+        value.deep_equals(&Value::Array(other.clone()), &self.mv8)
+    }
+
     /// Get the value using the given array index. Returns `Value::Undefined` if no element at the
     /// index exists.
     ///
@@ -46,17 +68,127 @@ impl Array {
         })
     }
 
+    /// Like `Array::get`, but returns `Error::OutOfBounds` if `index` is not less than the array's
+    /// length, rather than `Value::Undefined`.
+    pub fn try_get<V: FromValue>(&self, index: u32) -> Result<V> {
+        let len = self.len();
+        if index >= len {
+            return Err(Error::OutOfBounds { index: index as usize, len: len as usize });
+        }
+        self.get(index)
+    }
+
+    /// Like `Array::set`, but returns `Error::OutOfBounds` if `index` is greater than the array's
+    /// length, rather than growing the array with holes. Setting the element at the array's length
+    /// (i.e. pushing an element) is allowed.
+    pub fn try_set<V: ToValue>(&self, index: u32, value: V) -> Result<()> {
+        let len = self.len();
+        if index > len {
+            return Err(Error::OutOfBounds { index: index as usize, len: len as usize });
+        }
+        self.set(index, value)
+    }
+
     /// Returns the number of elements in the array.
     pub fn len(&self) -> u32 {
         self.mv8.scope(|scope| v8::Local::new(scope, self.handle.clone()).length())
     }
 
+    /// Returns `true` if the array has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Sets the length of the array, as assigning to JavaScript's `array.length` would. Shrinking
+    /// the array removes its trailing elements, and growing it leaves holes that read as
+    /// `undefined`.
+    ///
+    /// Returns an `Error::Value` holding the `TypeError` thrown by V8 if the length can't be set,
+    /// e.g. if the array is frozen, or if shrinking it would remove a non-configurable element.
+    pub fn set_length(&self, len: u32) -> Result<()> {
+        let set_length = self.mv8.builtin(|builtins| &builtins.array_set_length);
+        set_length.call((self.clone(), len))
+    }
+
     /// Pushes an element to the end of the array. This is a shortcut for `set` using `len` as the
     /// index.
     pub fn push<V: ToValue>(&self, value: V) -> Result<()> {
         self.set(self.len(), value)
     }
 
+    /// Returns a new array of `[a, b]` pairs, pairing each element of this array with the element
+    /// at the same index of `other`. The result is as long as the shorter of the two arrays.
+    pub fn zip(&self, other: &Array) -> Result<Array> {
+        let len = self.len().min(other.len());
+        let zipped = self.mv8.create_array();
+        for i in 0..len {
+            let pair = self.mv8.create_array();
+            pair.set(0, self.get::<Value>(i)?)?;
+            pair.set(1, other.get::<Value>(i)?)?;
+            zipped.set(i, pair)?;
+        }
+        Ok(zipped)
+    }
+
+    /// Splits an array of `[a, b]` pairs into an array of the first elements and an array of the
+    /// second elements. This is the inverse of `Array::zip`.
+    ///
+    /// Returns an error if any element is not an array.
+    pub fn unzip(&self) -> Result<(Array, Array)> {
+        let first = self.mv8.create_array();
+        let second = self.mv8.create_array();
+        for i in 0..self.len() {
+            let pair: Array = self.get(i)?;
+            first.set(i, pair.get::<Value>(0)?)?;
+            second.set(i, pair.get::<Value>(1)?)?;
+        }
+        Ok((first, second))
+    }
+
+    /// Returns a new array holding the elements from `start` up to (but not including) `end`, or
+    /// up to the end of the array if `end` is `None`, as JavaScript's `Array.prototype.slice`
+    /// would. Negative indices count back from the end of the array.
+    pub fn slice(&self, start: i64, end: Option<i64>) -> Result<Array> {
+        let end = end.map_or(Value::Undefined, |end| Value::Number(end as f64));
+        let slice = self.mv8.builtin(|builtins| &builtins.array_slice);
+        slice.call_method(self.clone(), (start, end))
+    }
+
+    /// Removes `delete_count` elements starting at `start`, and inserts `items` in their place, as
+    /// JavaScript's `Array.prototype.splice` would. Returns the removed elements.
+    pub fn splice(&self, start: u32, delete_count: u32, items: Values) -> Result<Array> {
+        let mut args = vec![Value::Number(start as f64), Value::Number(delete_count as f64)];
+        args.extend(items.into_vec());
+        let splice = self.mv8.builtin(|builtins| &builtins.array_splice);
+        splice.call_method(self.clone(), Values::from_vec(args))
+    }
+
+    /// Returns the index of the first element strictly equal (`===`) to `value`, or `-1` if there
+    /// is none, as JavaScript's `Array.prototype.indexOf` would. Since `NaN` is not equal to
+    /// itself, this never finds `NaN`; use `Array::contains` for that.
+    pub fn index_of(&self, value: &Value) -> Result<i64> {
+        let index_of = self.mv8.builtin(|builtins| &builtins.array_index_of);
+        index_of.call_method(self.clone(), (value.clone(),))
+    }
+
+    /// Returns `true` if the array contains `value`, as JavaScript's `Array.prototype.includes`
+    /// would. Unlike `Array::index_of`, this finds `NaN`.
+    pub fn contains(&self, value: &Value) -> Result<bool> {
+        let includes = self.mv8.builtin(|builtins| &builtins.array_includes);
+        includes.call_method(self.clone(), (value.clone(),))
+    }
+
+    /// Converts the array's elements into a `Vec`, reading the array's length once up front.
+    /// Returns the first error encountered while reading or converting an element.
+    pub fn to_vec<V: FromValue>(&self) -> Result<Vec<V>> {
+        let len = self.len();
+        let mut vec = Vec::with_capacity(len as usize);
+        for index in 0..len {
+            vec.push(self.get(index)?);
+        }
+        Ok(vec)
+    }
+
     /// Returns an iterator over the array's indexable values.
     pub fn elements<V: FromValue>(self) -> Elements<V> {
         Elements {
@@ -66,22 +198,54 @@ impl Array {
             _phantom: PhantomData,
         }
     }
+
+    /// Consumes the array and returns an iterator over the values produced by its iteration
+    /// protocol (its `Symbol.iterator` method), as JavaScript's `for...of` would. Unlike
+    /// `Array::elements`, this respects custom iteration behavior, e.g. of array subclasses. Values
+    /// are produced lazily, and iteration stops after the first error.
+    pub fn into_iter_values(self) -> IterValues {
+        IterValues { array: self, iterator: None, done: false }
+    }
+
+    fn iterator(&self) -> Result<(Object, Function)> {
+        let method: Function = self.mv8.try_catch(|scope| {
+            let array = v8::Local::new(scope, self.handle.clone());
+            let key = v8::Symbol::get_iterator(scope);
+            let result = array.get(scope, key.into());
+            self.mv8.exception(scope)?;
+            Ok(Value::from_v8_value(&self.mv8, scope, result.unwrap()))
+        }).and_then(|v| v.into(&self.mv8))?;
+        let iterator: Object = method.call_method(self.clone(), ())?;
+        let next = iterator.get("next")?;
+        Ok((iterator, next))
+    }
+}
+
+/// Compares arrays structurally with `Array::deep_equals`, which reads their elements and so may
+/// run JavaScript getters. Errors thrown while comparing are treated as inequality, and are not
+/// reported by `MiniV8::last_exception_details`.
+impl PartialEq for Array {
+    fn eq(&self, other: &Array) -> bool {
+        self.mv8.preserving_last_exception(|| self.deep_equals(other).unwrap_or(false))
+    }
 }
 
 impl fmt::Debug for Array {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let len = self.len();
-        write!(f, "[")?;
-        for i in 0..len {
-            match self.get::<Value>(i) {
-                Ok(v) => write!(f, "{:?}", v)?,
-                Err(_) => write!(f, "?")?,
-            };
-            if i + 1 < len {
-                write!(f, ", ")?;
+        debug_nested(&self.mv8, f, "[...]", |f| {
+            let len = self.len();
+            write!(f, "[")?;
+            for i in 0..len {
+                match self.get::<Value>(i) {
+                    Ok(v) => write!(f, "{:?}", v)?,
+                    Err(_) => write!(f, "?")?,
+                };
+                if i + 1 < len {
+                    write!(f, ", ")?;
+                }
             }
-        }
-        write!(f, "]")
+            write!(f, "]")
+        })
     }
 }
 
@@ -109,3 +273,41 @@ impl<V: FromValue> Iterator for Elements<V> {
         Some(result)
     }
 }
+
+/// An iterator over the values produced by an array's iteration protocol. See
+/// `Array::into_iter_values`.
+pub struct IterValues {
+    array: Array,
+    iterator: Option<(Object, Function)>,
+    done: bool,
+}
+
+impl IterValues {
+    fn step(&mut self) -> Result<Option<Value>> {
+        if self.iterator.is_none() {
+            self.iterator = Some(self.array.iterator()?);
+        }
+
+        let (iterator, next) = self.iterator.as_ref().unwrap();
+        let result: Object = next.call_method(iterator.clone(), ())?;
+        if result.get::<_, Value>("done")?.coerce_boolean(&self.array.mv8) {
+            Ok(None)
+        } else {
+            result.get("value").map(Some)
+        }
+    }
+}
+
+impl Iterator for IterValues {
+    type Item = Result<Value>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let result = self.step().transpose();
+        self.done = !matches!(result, Some(Ok(_)));
+        result
+    }
+}
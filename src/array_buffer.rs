@@ -0,0 +1,41 @@
+use crate::*;
+use std::fmt;
+
+/// Reference to a JavaScript `ArrayBuffer`, a fixed-length block of raw binary data.
+#[derive(Clone)]
+pub struct ArrayBuffer {
+    pub(crate) mv8: MiniV8,
+    pub(crate) handle: v8::Global<v8::ArrayBuffer>,
+}
+
+impl ArrayBuffer {
+    /// Consumes the array buffer and downgrades it to a JavaScript object.
+    pub fn into_object(self) -> Object {
+        self.mv8.clone().scope(|scope| {
+            let object: v8::Local<v8::Object> = v8::Local::new(scope, self.handle.clone()).into();
+            Object {
+                mv8: self.mv8,
+                handle: v8::Global::new(scope, object),
+            }
+        })
+    }
+
+    /// Returns the length of the buffer in bytes.
+    pub fn byte_length(&self) -> usize {
+        self.mv8.scope(|scope| v8::Local::new(scope, self.handle.clone()).byte_length())
+    }
+
+    /// Returns a copy of the buffer's contents.
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.mv8.scope(|scope| {
+            let backing_store = v8::Local::new(scope, self.handle.clone()).get_backing_store();
+            backing_store.iter().map(|byte| byte.get()).collect()
+        })
+    }
+}
+
+impl fmt::Debug for ArrayBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<arraybuffer of {} bytes>", self.byte_length())
+    }
+}
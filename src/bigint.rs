@@ -0,0 +1,73 @@
+use crate::*;
+use std::fmt;
+
+/// Reference to a JavaScript `BigInt`, an integer of arbitrary size.
+#[derive(Clone)]
+pub struct BigInt {
+    pub(crate) mv8: MiniV8,
+    pub(crate) handle: v8::Global<v8::BigInt>,
+}
+
+impl BigInt {
+    /// Returns the value as an `i128`, or `None` if it does not fit into an `i128`.
+    pub fn to_i128(&self) -> Option<i128> {
+        let (negative, magnitude) = self.to_sign_magnitude()?;
+        if !negative {
+            i128::try_from(magnitude).ok()
+        } else if magnitude <= i128::MIN.unsigned_abs() {
+            Some(0i128.wrapping_sub(magnitude as i128))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the value as a `u128`, or `None` if it is negative or does not fit into a `u128`.
+    pub fn to_u128(&self) -> Option<u128> {
+        match self.to_sign_magnitude()? {
+            (false, magnitude) => Some(magnitude),
+            (true, _) => None,
+        }
+    }
+
+    pub(crate) fn from_sign_magnitude(mv8: &MiniV8, negative: bool, magnitude: u128) -> BigInt {
+        mv8.scope(|scope| {
+            let words = [magnitude as u64, (magnitude >> 64) as u64];
+            let bigint = v8::BigInt::new_from_words(scope, negative, &words).unwrap();
+            BigInt {
+                mv8: mv8.clone(),
+                handle: v8::Global::new(scope, bigint),
+            }
+        })
+    }
+
+    // Returns the sign and the absolute value of the `BigInt`, or `None` if the absolute value does
+    // not fit into a `u128`.
+    fn to_sign_magnitude(&self) -> Option<(bool, u128)> {
+        self.mv8.scope(|scope| {
+            let bigint = v8::Local::new(scope, self.handle.clone());
+            let word_count = bigint.word_count();
+            if word_count > 2 {
+                return None;
+            }
+
+            let mut words = [0u64; 2];
+            let (negative, words) = bigint.to_words_array(&mut words[..word_count]);
+            let magnitude = words.iter().enumerate()
+                .fold(0u128, |acc, (i, &word)| acc | ((word as u128) << (64 * i)));
+            Some((negative && magnitude != 0, magnitude))
+        })
+    }
+}
+
+impl fmt::Debug for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let digits = self.mv8.scope(|scope| {
+            let bigint: v8::Local<v8::Value> = v8::Local::new(scope, self.handle.clone()).into();
+            bigint.to_string(scope).map(|s| s.to_rust_string_lossy(scope))
+        });
+        match digits {
+            Some(digits) => write!(f, "{}n", digits),
+            None => write!(f, "<bigint>"),
+        }
+    }
+}
@@ -1,7 +1,9 @@
 use crate::*;
 use std::collections::{BTreeMap, HashMap, BTreeSet, HashSet};
 use std::hash::{BuildHasher, Hash};
+use std::rc::Rc;
 use std::string::String as StdString;
+use std::sync::Arc;
 use std::time::Duration;
 
 impl ToValue for Value {
@@ -46,6 +48,30 @@ impl<T: FromValue> FromValue for Option<T> {
     }
 }
 
+impl<T: ToValue> ToValue for Box<T> {
+    fn to_value(self, mv8: &MiniV8) -> Result<Value> {
+        (*self).to_value(mv8)
+    }
+}
+
+impl<T: FromValue> FromValue for Box<T> {
+    fn from_value(value: Value, mv8: &MiniV8) -> Result<Self> {
+        Ok(Box::new(T::from_value(value, mv8)?))
+    }
+}
+
+impl<T: ToValue + Clone> ToValue for Rc<T> {
+    fn to_value(self, mv8: &MiniV8) -> Result<Value> {
+        Rc::try_unwrap(self).unwrap_or_else(|rc| (*rc).clone()).to_value(mv8)
+    }
+}
+
+impl<T: ToValue + Clone> ToValue for Arc<T> {
+    fn to_value(self, mv8: &MiniV8) -> Result<Value> {
+        Arc::try_unwrap(self).unwrap_or_else(|arc| (*arc).clone()).to_value(mv8)
+    }
+}
+
 impl ToValue for String {
     fn to_value(self, _mv8: &MiniV8) -> Result<Value> {
         Ok(Value::String(self))
@@ -58,6 +84,21 @@ impl FromValue for String {
     }
 }
 
+impl ToValue for Symbol {
+    fn to_value(self, _mv8: &MiniV8) -> Result<Value> {
+        Ok(Value::Symbol(self))
+    }
+}
+
+impl FromValue for Symbol {
+    fn from_value(value: Value, _mv8: &MiniV8) -> Result<Symbol> {
+        match value {
+            Value::Symbol(s) => Ok(s),
+            value => Err(Error::from_js_conversion(value.type_name(), "Symbol")),
+        }
+    }
+}
+
 impl ToValue for Array {
     fn to_value(self, _mv8: &MiniV8) -> Result<Value> {
         Ok(Value::Array(self))
@@ -73,6 +114,36 @@ impl FromValue for Array {
     }
 }
 
+impl ToValue for ArrayBuffer {
+    fn to_value(self, _mv8: &MiniV8) -> Result<Value> {
+        Ok(Value::ArrayBuffer(self))
+    }
+}
+
+impl FromValue for ArrayBuffer {
+    fn from_value(value: Value, _mv8: &MiniV8) -> Result<ArrayBuffer> {
+        match value {
+            Value::ArrayBuffer(b) => Ok(b),
+            value => Err(Error::from_js_conversion(value.type_name(), "ArrayBuffer")),
+        }
+    }
+}
+
+impl ToValue for TypedArray {
+    fn to_value(self, _mv8: &MiniV8) -> Result<Value> {
+        Ok(Value::TypedArray(self))
+    }
+}
+
+impl FromValue for TypedArray {
+    fn from_value(value: Value, _mv8: &MiniV8) -> Result<TypedArray> {
+        match value {
+            Value::TypedArray(t) => Ok(t),
+            value => Err(Error::from_js_conversion(value.type_name(), "TypedArray")),
+        }
+    }
+}
+
 impl ToValue for Function {
     fn to_value(self, _mv8: &MiniV8) -> Result<Value> {
         Ok(Value::Function(self))
@@ -88,6 +159,96 @@ impl FromValue for Function {
     }
 }
 
+impl ToValue for Promise {
+    fn to_value(self, _mv8: &MiniV8) -> Result<Value> {
+        Ok(Value::Promise(self))
+    }
+}
+
+impl FromValue for Promise {
+    fn from_value(value: Value, _mv8: &MiniV8) -> Result<Promise> {
+        match value {
+            Value::Promise(p) => Ok(p),
+            value => Err(Error::from_js_conversion(value.type_name(), "Promise")),
+        }
+    }
+}
+
+impl ToValue for Map {
+    fn to_value(self, _mv8: &MiniV8) -> Result<Value> {
+        Ok(Value::Map(self))
+    }
+}
+
+impl FromValue for Map {
+    fn from_value(value: Value, _mv8: &MiniV8) -> Result<Map> {
+        match value {
+            Value::Map(m) => Ok(m),
+            value => Err(Error::from_js_conversion(value.type_name(), "Map")),
+        }
+    }
+}
+
+impl ToValue for Set {
+    fn to_value(self, _mv8: &MiniV8) -> Result<Value> {
+        Ok(Value::Set(self))
+    }
+}
+
+impl FromValue for Set {
+    fn from_value(value: Value, _mv8: &MiniV8) -> Result<Set> {
+        match value {
+            Value::Set(s) => Ok(s),
+            value => Err(Error::from_js_conversion(value.type_name(), "Set")),
+        }
+    }
+}
+
+impl ToValue for WeakMap {
+    fn to_value(self, _mv8: &MiniV8) -> Result<Value> {
+        Ok(Value::WeakMap(self))
+    }
+}
+
+impl FromValue for WeakMap {
+    fn from_value(value: Value, _mv8: &MiniV8) -> Result<WeakMap> {
+        match value {
+            Value::WeakMap(m) => Ok(m),
+            value => Err(Error::from_js_conversion(value.type_name(), "WeakMap")),
+        }
+    }
+}
+
+impl ToValue for WeakSet {
+    fn to_value(self, _mv8: &MiniV8) -> Result<Value> {
+        Ok(Value::WeakSet(self))
+    }
+}
+
+impl FromValue for WeakSet {
+    fn from_value(value: Value, _mv8: &MiniV8) -> Result<WeakSet> {
+        match value {
+            Value::WeakSet(s) => Ok(s),
+            value => Err(Error::from_js_conversion(value.type_name(), "WeakSet")),
+        }
+    }
+}
+
+impl ToValue for RegExp {
+    fn to_value(self, _mv8: &MiniV8) -> Result<Value> {
+        Ok(Value::RegExp(self))
+    }
+}
+
+impl FromValue for RegExp {
+    fn from_value(value: Value, _mv8: &MiniV8) -> Result<RegExp> {
+        match value {
+            Value::RegExp(r) => Ok(r),
+            value => Err(Error::from_js_conversion(value.type_name(), "RegExp")),
+        }
+    }
+}
+
 impl ToValue for Object {
     fn to_value(self, _mv8: &MiniV8) -> Result<Value> {
         Ok(Value::Object(self))
@@ -103,6 +264,23 @@ impl FromValue for Object {
     }
 }
 
+impl ToValue for BigInt {
+    fn to_value(self, _mv8: &MiniV8) -> Result<Value> {
+        Ok(Value::BigInt(self))
+    }
+}
+
+impl FromValue for BigInt {
+    fn from_value(value: Value, _mv8: &MiniV8) -> Result<BigInt> {
+        match value {
+            Value::BigInt(b) => Ok(b),
+            value => Err(Error::from_js_conversion(value.type_name(), "BigInt")),
+        }
+    }
+}
+
+/// Converts into an object whose properties are the map's entries. A `HashMap` has no defined
+/// iteration order, so the order of the object's properties is unspecified.
 impl<K, V, S> ToValue for HashMap<K, V, S>
 where
     K: Eq + Hash + ToValue,
@@ -110,11 +288,7 @@ where
     S: BuildHasher,
 {
     fn to_value(self, mv8: &MiniV8) -> Result<Value> {
-        let object = mv8.create_object();
-        for (k, v) in self.into_iter() {
-            object.set(k, v)?;
-        }
-        Ok(Value::Object(object))
+        Ok(Value::Object(mv8.create_object_from(self)?))
     }
 }
 
@@ -138,11 +312,7 @@ where
     V: ToValue,
 {
     fn to_value(self, mv8: &MiniV8) -> Result<Value> {
-        let object = mv8.create_object();
-        for (k, v) in self.into_iter() {
-            object.set(k, v)?;
-        }
-        Ok(Value::Object(object))
+        Ok(Value::Object(mv8.create_object_from(self)?))
     }
 }
 
@@ -199,11 +369,13 @@ impl<V: FromValue + Hash + Eq> FromValue for HashSet<V> {
 
 impl<V: ToValue> ToValue for Vec<V> {
     fn to_value(self, mv8: &MiniV8) -> Result<Value> {
-        let array = mv8.create_array();
-        for v in self.into_iter() {
-            array.push(v)?;
-        }
-        Ok(Value::Array(array))
+        Ok(Value::Array(mv8.create_array_from(self)?))
+    }
+}
+
+impl<V: ToValue + Clone> ToValue for &[V] {
+    fn to_value(self, mv8: &MiniV8) -> Result<Value> {
+        self.to_vec().to_value(mv8)
     }
 }
 
@@ -240,7 +412,7 @@ impl FromValue for StdString {
     }
 }
 
-impl<'a> ToValue for &'a str {
+impl ToValue for &str {
     fn to_value(self, mv8: &MiniV8) -> Result<Value> {
         Ok(Value::String(mv8.create_string(self)))
     }
@@ -262,19 +434,150 @@ macro_rules! convert_number {
     }
 }
 
-convert_number!(i8);
-convert_number!(u8);
-convert_number!(i16);
-convert_number!(u16);
-convert_number!(i32);
-convert_number!(u32);
-convert_number!(i64);
-convert_number!(u64);
-convert_number!(isize);
-convert_number!(usize);
+// Converts a value to an integer type without losing information: the value is coerced to a
+// number, which must be integral and within the type's own range.
+macro_rules! integer_from_value {
+    ($value: expr, $mv8: expr, $prim_ty: ty) => {{
+        // `MAX as f64 + 1.0` is exact, or rounds to exactly `MAX + 1` for types wider than 53 bits:
+        let range = <$prim_ty>::MIN as f64..<$prim_ty>::MAX as f64 + 1.0;
+        let number = $value.coerce_integral($mv8, range, stringify!($prim_ty))?;
+        Ok(number as $prim_ty)
+    }}
+}
+
+macro_rules! convert_integer {
+    ($prim_ty: ty) => {
+        impl ToValue for $prim_ty {
+            fn to_value(self, _mv8: &MiniV8) -> Result<Value> {
+                Ok(Value::Number(self as f64))
+            }
+        }
+
+        impl FromValue for $prim_ty {
+            fn from_value(value: Value, mv8: &MiniV8) -> Result<Self> {
+                integer_from_value!(value, mv8, $prim_ty)
+            }
+        }
+    }
+}
+
+macro_rules! convert_checked_integer {
+    ($prim_ty: ty) => {
+        impl FromValue for Checked<$prim_ty> {
+            fn from_value(value: Value, mv8: &MiniV8) -> Result<Self> {
+                <$prim_ty>::from_value(value, mv8).map(Checked)
+            }
+        }
+    }
+}
+
+convert_checked_integer!(i8);
+convert_checked_integer!(u8);
+convert_checked_integer!(i16);
+convert_checked_integer!(u16);
+convert_checked_integer!(i32);
+convert_checked_integer!(u32);
+convert_checked_integer!(i64);
+convert_checked_integer!(u64);
+convert_checked_integer!(isize);
+convert_checked_integer!(usize);
+
+impl FromValue for Checked<f32> {
+    fn from_value(value: Value, mv8: &MiniV8) -> Result<Self> {
+        let type_name = value.type_name();
+        let number = value.coerce_number(mv8)?;
+        if number.is_finite() && number.abs() <= f32::MAX as f64 {
+            Ok(Checked(number as f32))
+        } else {
+            Err(Error::from_js_conversion(type_name, "f32"))
+        }
+    }
+}
+
+impl FromValue for Checked<f64> {
+    fn from_value(value: Value, mv8: &MiniV8) -> Result<Self> {
+        let type_name = value.type_name();
+        let number = value.coerce_number(mv8)?;
+        if number.is_finite() {
+            Ok(Checked(number))
+        } else {
+            Err(Error::from_js_conversion(type_name, "f64"))
+        }
+    }
+}
+
+impl<T: ToValue> ToValue for Checked<T> {
+    fn to_value(self, mv8: &MiniV8) -> Result<Value> {
+        self.0.to_value(mv8)
+    }
+}
+
+convert_integer!(i8);
+convert_integer!(u8);
+convert_integer!(i16);
+convert_integer!(u16);
+convert_integer!(i32);
+convert_integer!(u32);
+convert_integer!(i64);
+convert_integer!(u64);
+convert_integer!(isize);
+convert_integer!(usize);
 convert_number!(f32);
 convert_number!(f64);
 
+impl ToValue for i128 {
+    fn to_value(self, mv8: &MiniV8) -> Result<Value> {
+        Ok(Value::BigInt(BigInt::from_sign_magnitude(mv8, self < 0, self.unsigned_abs())))
+    }
+}
+
+impl FromValue for i128 {
+    fn from_value(value: Value, mv8: &MiniV8) -> Result<Self> {
+        match value {
+            Value::BigInt(b) => {
+                b.to_i128().ok_or_else(|| Error::from_js_conversion("bigint", "i128"))
+            },
+            value => integer_from_value!(value, mv8, i128),
+        }
+    }
+}
+
+impl ToValue for u128 {
+    fn to_value(self, mv8: &MiniV8) -> Result<Value> {
+        Ok(Value::BigInt(BigInt::from_sign_magnitude(mv8, false, self)))
+    }
+}
+
+impl FromValue for u128 {
+    fn from_value(value: Value, mv8: &MiniV8) -> Result<Self> {
+        match value {
+            Value::BigInt(b) => {
+                b.to_u128().ok_or_else(|| Error::from_js_conversion("bigint", "u128"))
+            },
+            value => integer_from_value!(value, mv8, u128),
+        }
+    }
+}
+
+/// Converts into a `Value::Date`. JavaScript dates have millisecond precision, so any
+/// sub-millisecond part of the timestamp is truncated.
+#[cfg(feature = "chrono")]
+impl ToValue for chrono::DateTime<chrono::Utc> {
+    fn to_value(self, _mv8: &MiniV8) -> Result<Value> {
+        Ok(Value::Date(self.timestamp_millis() as f64))
+    }
+}
+
+/// Converts from a `Value::Date`. Returns an error for any other value, or for an invalid date
+/// (i.e. one whose timestamp is `NaN`).
+#[cfg(feature = "chrono")]
+impl FromValue for chrono::DateTime<chrono::Utc> {
+    fn from_value(value: Value, _mv8: &MiniV8) -> Result<Self> {
+        let type_name = value.type_name();
+        value.as_chrono().ok_or_else(|| Error::from_js_conversion(type_name, "DateTime"))
+    }
+}
+
 impl ToValue for Duration {
     fn to_value(self, _mv8: &MiniV8) -> Result<Value> {
         Ok(Value::Date((self.as_secs() as f64) + (self.as_nanos() as f64) / 1_000_000_000.0))
@@ -429,3 +732,29 @@ impl_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M);
 impl_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, N);
 impl_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O);
 impl_tuple!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P);
+
+macro_rules! impl_named_fields {
+    ($($key:ident: $name:ident),*) => (
+        impl<$($key, $name),*> ToValue for NamedFields<($(($key, $name),)*)>
+        where
+            $($key: ToValue, $name: ToValue,)*
+        {
+            #[allow(non_snake_case)]
+            fn to_value(self, mv8: &MiniV8) -> Result<Value> {
+                let object = mv8.create_object();
+                let ($(($key, $name),)*) = self.0;
+                $(object.set($key, $name)?;)*
+                Ok(Value::Object(object))
+            }
+        }
+    )
+}
+
+impl_named_fields!(KA: A);
+impl_named_fields!(KA: A, KB: B);
+impl_named_fields!(KA: A, KB: B, KC: C);
+impl_named_fields!(KA: A, KB: B, KC: C, KD: D);
+impl_named_fields!(KA: A, KB: B, KC: C, KD: D, KE: E);
+impl_named_fields!(KA: A, KB: B, KC: C, KD: D, KE: E, KF: F);
+impl_named_fields!(KA: A, KB: B, KC: C, KD: D, KE: E, KF: F, KG: G);
+impl_named_fields!(KA: A, KB: B, KC: C, KD: D, KE: E, KF: F, KG: G, KH: H);
@@ -2,6 +2,7 @@ use crate::*;
 use std::error::Error as StdError;
 use std::fmt;
 use std::result::Result as StdResult;
+use std::string::String as StdString;
 
 /// `std::result::Result` specialized for this crate's `Error` type.
 pub type Result<T> = StdResult<T, Error>;
@@ -32,6 +33,45 @@ pub enum Error {
     RecursiveMutCallback,
     /// An evaluation timeout was specified from within a Rust function embedded in V8.
     InvalidTimeout,
+    /// An index or range was outside the bounds of the collection it was used with.
+    OutOfBounds {
+        /// The index (or the end of the range) that was out of bounds.
+        index: usize,
+        /// The length of the collection.
+        len: usize,
+    },
+    /// A sandboxed evaluation exceeded its heap size limit.
+    HeapLimit,
+    /// A sandboxed evaluation was requested from within a Rust function embedded in V8.
+    InvalidSandbox,
+    /// A function created with `MiniV8::create_revocable_function` was called after being revoked.
+    Revoked,
+    /// A Rust function was called while the maximum depth of nested Rust function calls set with
+    /// `MiniV8::set_max_callback_depth` had been reached.
+    MaxDepthExceeded,
+    /// A property could not be set while building an object from an iterator of properties (see
+    /// `MiniV8::create_object_from`).
+    PropertyError {
+        /// The position of the offending property within the iterator.
+        index: usize,
+        /// The offending property's key, or `None` if the key could not be converted to a string.
+        key: Option<StdString>,
+        /// The error that occurred while setting the property.
+        source: Box<Error>,
+    },
+    /// `MiniV8::request_gc` was called without V8's `--expose-gc` flag set.
+    GcUnavailable,
+    /// `MiniV8::stop_cpu_profile` was called while no CPU profile was being recorded.
+    NoCpuProfile,
+    /// JavaScript code exceeded the maximum call stack size, e.g. through runaway recursion. This
+    /// holds the `RangeError` V8 threw, which is what JavaScript sees if the error is returned
+    /// from a Rust function called by JavaScript.
+    ///
+    /// V8 doesn't flag stack overflow errors, so they are recognized by V8's message for them
+    /// ("Maximum call stack size exceeded"). A script that throws its own `RangeError` with the
+    /// same message is indistinguishable from one that overflowed, and a future V8 version that
+    /// rewords the message would yield `Error::Value` instead.
+    StackOverflow(Value),
     /// A custom error that occurs during runtime.
     ///
     /// This can be used for returning user-defined errors from callbacks.
@@ -42,22 +82,42 @@ pub enum Error {
 
 impl Error {
     /// Normalizes an error into a JavaScript value.
+    ///
+    /// Errors other than `Error::Value` become native JavaScript `Error` instances (`TypeError`
+    /// instances for conversion errors and `Error::Revoked`), complete with a `stack` property.
     pub fn to_value(self, mv8: &MiniV8) -> Value {
-        match self {
-            Error::Value(value) => value,
+        let type_error = match self {
+            Error::Value(value) | Error::StackOverflow(value) => return value,
             Error::ToJsConversionError { .. } |
-            Error::FromJsConversionError { .. } => {
-                let object = mv8.create_object();
-                let _ = object.set("name", "TypeError");
-                let _ = object.set("message", self.to_string());
-                Value::Object(object)
-            },
-            _ => {
-                let object = mv8.create_object();
-                let _ = object.set("name", "Error");
-                let _ = object.set("message", self.to_string());
-                Value::Object(object)
+            Error::FromJsConversionError { .. } |
+            Error::Revoked => true,
+            _ => false,
+        };
+
+        let message = self.to_string();
+        mv8.scope(|scope| {
+            let message = v8::String::new(scope, &message).unwrap();
+            let error = match type_error {
+                true => v8::Exception::type_error(scope, message),
+                false => v8::Exception::error(scope, message),
+            };
+            Value::from_v8_value(mv8, scope, error)
+        })
+    }
+
+    /// Returns the thrown value if this is an exception whose thrown value is a primitive (e.g.
+    /// from `throw 42` or `throw "message"`) rather than an object, `None` otherwise.
+    ///
+    /// Such exceptions have no `name`, `message` or `stack` properties, so code inspecting errors
+    /// should check for them before assuming an `Error` object.
+    pub fn thrown_primitive(&self) -> Option<&Value> {
+        match self {
+            Error::Value(value) => match value {
+                Value::Undefined | Value::Null | Value::Boolean(_) | Value::Number(_) |
+                Value::BigInt(_) | Value::String(_) | Value::Symbol(_) => Some(value),
+                _ => None,
             },
+            _ => None,
         }
     }
 
@@ -70,6 +130,13 @@ impl StdError for Error {
     fn description(&self) -> &'static str {
         "JavaScript execution error"
     }
+
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Error::PropertyError { source, .. } => Some(&**source),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for Error {
@@ -84,8 +151,27 @@ impl fmt::Display for Error {
             Error::Timeout => write!(fmt, "evaluation timed out"),
             Error::RecursiveMutCallback => write!(fmt, "mutable callback called recursively"),
             Error::InvalidTimeout => write!(fmt, "invalid request for evaluation timeout"),
+            Error::OutOfBounds { index, len } => {
+                write!(fmt, "index {} out of bounds for length {}", index, len)
+            },
+            Error::HeapLimit => write!(fmt, "evaluation exceeded heap limit"),
+            Error::InvalidSandbox => write!(fmt, "invalid request for sandboxed evaluation"),
+            Error::Revoked => write!(fmt, "function has been revoked"),
+            Error::MaxDepthExceeded => write!(fmt, "maximum callback depth exceeded"),
+            Error::PropertyError { index, key: Some(key), source } => {
+                write!(fmt, "error setting property {:?} at index {}: {}", key, index, source)
+            },
+            Error::PropertyError { index, key: None, source } => {
+                write!(fmt, "error setting property at index {}: {}", index, source)
+            },
+            Error::GcUnavailable => write!(fmt, "garbage collection requires the --expose-gc flag"),
+            Error::NoCpuProfile => write!(fmt, "no CPU profile is being recorded"),
+            Error::StackOverflow(_) => write!(fmt, "maximum call stack size exceeded"),
             Error::ExternalError(ref err) => err.fmt(fmt),
-            Error::Value(v) => write!(fmt, "JavaScript runtime error ({})", v.type_name()),
+            Error::Value(v) => match self.thrown_primitive() {
+                Some(_) => write!(fmt, "JavaScript runtime error ({}: {:?})", v.type_name(), v),
+                None => write!(fmt, "JavaScript runtime error ({})", v.type_name()),
+            },
         }
     }
 }
@@ -1,5 +1,7 @@
 use crate::*;
+use std::cell::Cell;
 use std::fmt;
+use std::rc::Rc;
 
 #[derive(Clone)]
 pub struct Function {
@@ -19,6 +21,29 @@ impl Function {
         })
     }
 
+    /// Returns `true` if this and `other` refer to the same JavaScript function, as `===` would.
+    /// Separate functions are never equal, even if their contents are identical. This is also
+    /// how `PartialEq` compares functions: functions have no structure to compare, so
+    /// `Value::deep_equals` compares them by identity too, unlike arrays and objects.
+    pub fn ptr_eq(&self, other: &Function) -> bool {
+        self.mv8.scope(|scope| {
+            let value = v8::Local::new(scope, self.handle.clone());
+            let other = v8::Local::new(scope, other.handle.clone());
+            value.strict_equals(other.into())
+        })
+    }
+
+    /// Returns the function's name (its `name` property), which is empty for anonymous functions
+    /// and for functions created with `MiniV8::create_function`.
+    pub fn name(&self) -> Result<String> {
+        self.clone().into_object().get("name")
+    }
+
+    /// Returns the number of parameters declared by the function (its `length` property).
+    pub fn arity(&self) -> Result<u32> {
+        self.clone().into_object().get("length")
+    }
+
     /// Calls the function with the given arguments, with `this` set to `undefined`.
     pub fn call<A, R>(&self, args: A) -> Result<R>
     where
@@ -48,6 +73,31 @@ impl Function {
         }).and_then(|v| v.into(&self.mv8))
     }
 
+    /// Calls the function with the given `this` and arguments, where the number of arguments need
+    /// not be known at compile time.
+    pub fn apply<R: FromValue>(&self, this: Value, args: Vec<Value>) -> Result<R> {
+        self.call_method(this, Values::from_vec(args))
+    }
+
+    /// Calls the function with the given `this` and the elements of the given array as arguments,
+    /// with the semantics of JavaScript's `Reflect.apply`.
+    ///
+    /// Unlike calling the function's `apply` method from JavaScript, this can't be intercepted by
+    /// overriding `apply` on the function or on `Function.prototype` (nor by replacing
+    /// `Reflect.apply`), which makes it suitable for calling untrusted functions.
+    pub fn reflect_apply(&self, this: Value, args: &Array) -> Result<Value> {
+        let args = args.clone().elements().collect::<Result<Vec<Value>>>()?;
+        self.apply(this, args)
+    }
+
+    /// Calls the function as a constructor function with the elements of the given array as
+    /// arguments, with the semantics of JavaScript's `Reflect.construct` (i.e. `new f(...args)`).
+    /// This complements `call_new` for argument lists whose length is only known at runtime.
+    pub fn construct_apply(&self, args: &Array) -> Result<Value> {
+        let args = args.clone().elements().collect::<Result<Vec<Value>>>()?;
+        self.call_new(Values::from_vec(args))
+    }
+
     /// Calls the function as a constructor function with the given arguments.
     pub fn call_new<A, R>(&self, args: A) -> Result<R>
     where
@@ -66,9 +116,52 @@ impl Function {
     }
 }
 
+/// Compares functions by identity with `Function::ptr_eq`, which is also how `Value::deep_equals`
+/// compares them. Unlike comparing arrays and objects, this never runs JavaScript.
+impl PartialEq for Function {
+    fn eq(&self, other: &Function) -> bool {
+        self.ptr_eq(other)
+    }
+}
+
+impl Eq for Function {}
+
 impl fmt::Debug for Function {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "<function>")
+        // Reading `name` or `length` can throw (e.g. if they were redefined as accessors), in which
+        // case we fall back to placeholders rather than failing to format:
+        self.mv8.preserving_last_exception(|| {
+            let name = match self.name() {
+                Ok(name) if !name.to_string().is_empty() => name.to_string(),
+                _ => "<anonymous>".to_owned(),
+            };
+            match self.arity() {
+                Ok(arity) => write!(f, "<function {}({})>", name, arity),
+                Err(_) => write!(f, "<function {}(?)>", name),
+            }
+        })
+    }
+}
+
+/// A handle for revoking a function created with `MiniV8::create_revocable_function`.
+#[derive(Clone)]
+pub struct RevokeHandle(pub(crate) Rc<Cell<bool>>);
+
+impl RevokeHandle {
+    /// Revokes the function. Any later calls to it throw instead of calling the Rust closure.
+    pub fn revoke(&self) {
+        self.0.set(true);
+    }
+
+    /// Returns `true` if the function has been revoked.
+    pub fn is_revoked(&self) -> bool {
+        self.0.get()
+    }
+}
+
+impl fmt::Debug for RevokeHandle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "RevokeHandle {{ revoked: {} }}", self.is_revoked())
     }
 }
 
@@ -81,4 +174,18 @@ pub struct Invocation {
     pub this: Value,
     /// The list of arguments with which the function was called.
     pub args: Values,
+    /// Whether the function was called as a constructor function (i.e. with `new`).
+    pub is_construct_call: bool,
+    /// The value of `new.target` within the function invocation, which is the constructor function
+    /// that `new` was invoked on, or `undefined` if the function was not called with `new`.
+    pub new_target: Value,
+}
+
+impl Invocation {
+    /// Returns the arguments from `index` onward, or an empty `Values` if there are no more than
+    /// `index` arguments. This is useful for implementing functions with rest parameters (e.g.
+    /// `function(first, ...rest)`).
+    pub fn rest_from(&self, index: usize) -> Values {
+        self.args.iter().skip(index).cloned().collect()
+    }
 }
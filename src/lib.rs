@@ -1,19 +1,46 @@
 //! MiniV8 is a minimal embedded V8 JavaScript engine wrapper for Rust.
 
+mod allocator;
 mod array;
+mod array_buffer;
+mod bigint;
 mod conversion;
 mod error;
 mod function;
+mod map;
 mod mini_v8;
 mod object;
+mod profiler;
+mod promise;
+mod regexp;
+#[cfg(feature = "serde")] pub mod serde;
+mod set;
 mod string;
+mod symbol;
 #[cfg(test)] mod tests;
+mod typed_array;
 mod value;
+mod weak;
+mod weak_map;
+mod weak_set;
 
+pub use crate::allocator::BufferAllocator;
 pub use crate::array::*;
+pub use crate::array_buffer::*;
+pub use crate::bigint::*;
 pub use crate::error::*;
 pub use crate::function::*;
+pub use crate::map::*;
 pub use crate::mini_v8::*;
 pub use crate::object::*;
+pub use crate::profiler::{CpuProfile, ProfiledFunction};
+pub use crate::promise::*;
+pub use crate::regexp::*;
+pub use crate::set::*;
 pub use crate::string::*;
+pub use crate::symbol::*;
+pub use crate::typed_array::*;
 pub use crate::value::*;
+pub use crate::weak::*;
+pub use crate::weak_map::*;
+pub use crate::weak_set::*;
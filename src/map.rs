@@ -0,0 +1,167 @@
+use crate::*;
+use std::fmt;
+use std::marker::PhantomData;
+
+/// Reference to a JavaScript `Map`.
+#[derive(Clone)]
+pub struct Map {
+    pub(crate) mv8: MiniV8,
+    pub(crate) handle: v8::Global<v8::Map>,
+}
+
+impl Map {
+    /// Consumes the map and downgrades it to a JavaScript object.
+    pub fn into_object(self) -> Object {
+        self.mv8.clone().scope(|scope| {
+            let object: v8::Local<v8::Object> = v8::Local::new(scope, self.handle.clone()).into();
+            Object {
+                mv8: self.mv8,
+                handle: v8::Global::new(scope, object),
+            }
+        })
+    }
+
+    /// Get the value associated with the given key. Returns `Value::Undefined` if no entry with the
+    /// key exists.
+    ///
+    /// Returns an error if `ToValue::to_value` fails for the key or if `FromValue::from_value`
+    /// fails for the value.
+    pub fn get<K: ToValue, V: FromValue>(&self, key: K) -> Result<V> {
+        let key = key.to_value(&self.mv8)?;
+        self.mv8.try_catch(|scope| {
+            let map = v8::Local::new(scope, self.handle.clone());
+            let key = key.to_v8_value(scope);
+            let result = map.get(scope, key);
+            self.mv8.exception(scope)?;
+            Ok(Value::from_v8_value(&self.mv8, scope, result.unwrap()))
+        }).and_then(|v| v.into(&self.mv8))
+    }
+
+    /// Associates the given value with the given key, replacing any existing entry with the key.
+    ///
+    /// Returns an error if `ToValue::to_value` fails for either the key or the value.
+    pub fn set<K: ToValue, V: ToValue>(&self, key: K, value: V) -> Result<()> {
+        let key = key.to_value(&self.mv8)?;
+        let value = value.to_value(&self.mv8)?;
+        self.mv8.try_catch(|scope| {
+            let map = v8::Local::new(scope, self.handle.clone());
+            let key = key.to_v8_value(scope);
+            let value = value.to_v8_value(scope);
+            map.set(scope, key, value);
+            self.mv8.exception(scope)
+        })
+    }
+
+    /// Removes the entry with the given key from the map, like JavaScript's
+    /// `Map.prototype.delete`. Returns `true` if an entry was removed, `false` if no entry with the
+    /// key existed.
+    ///
+    /// Returns an error if `ToValue::to_value` fails for the key.
+    pub fn remove<K: ToValue>(&self, key: K) -> Result<bool> {
+        let key = key.to_value(&self.mv8)?;
+        self.mv8.try_catch(|scope| {
+            let map = v8::Local::new(scope, self.handle.clone());
+            let key = key.to_v8_value(scope);
+            let removed = map.delete(scope, key);
+            self.mv8.exception(scope)?;
+            Ok(removed.unwrap())
+        })
+    }
+
+    /// Returns `true` if the map has an entry with the given key, `false` otherwise.
+    ///
+    /// Returns an error if `ToValue::to_value` fails for the key.
+    pub fn has<K: ToValue>(&self, key: K) -> Result<bool> {
+        let key = key.to_value(&self.mv8)?;
+        self.mv8.try_catch(|scope| {
+            let map = v8::Local::new(scope, self.handle.clone());
+            let key = key.to_v8_value(scope);
+            let has = map.has(scope, key);
+            self.mv8.exception(scope)?;
+            Ok(has.unwrap())
+        })
+    }
+
+    /// Returns the number of entries in the map (like JavaScript's `Map.prototype.size`).
+    pub fn len(&self) -> usize {
+        self.mv8.scope(|scope| v8::Local::new(scope, self.handle.clone()).size())
+    }
+
+    /// Returns `true` if the map has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Converts the map into an iterator over its keys and values, in insertion order.
+    ///
+    /// The entries are read when this method is called; later changes to the map are not
+    /// reflected by the iterator.
+    pub fn entries<K: FromValue, V: FromValue>(self) -> MapEntries<K, V> {
+        let entries = self.mv8.scope(|scope| {
+            let entries = v8::Local::new(scope, self.handle.clone()).as_array(scope);
+            Array {
+                mv8: self.mv8.clone(),
+                handle: v8::Global::new(scope, entries),
+            }
+        });
+        MapEntries { entries, index: 0, _phantom: PhantomData }
+    }
+}
+
+impl fmt::Debug for Map {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.mv8.preserving_last_exception(|| {
+            let entries = self.clone().entries::<Value, Value>().collect::<Result<Vec<_>>>();
+            let entries = match entries {
+                Ok(entries) => entries,
+                Err(_) => return write!(f, "<map with entries exception>"),
+            };
+
+            if entries.is_empty() {
+                return write!(f, "Map {{}}");
+            }
+
+            write!(f, "Map {{ ")?;
+            for (i, (k, v)) in entries.iter().enumerate() {
+                write!(f, "{:?} => {:?}", k, v)?;
+                if i + 1 < entries.len() {
+                    write!(f, ", ")?;
+                }
+            }
+            write!(f, " }}")
+        })
+    }
+}
+
+/// An iterator over a map's keys and values.
+pub struct MapEntries<K, V> {
+    // A flat array of alternating keys and values.
+    entries: Array,
+    index: u32,
+    _phantom: PhantomData<(K, V)>,
+}
+
+impl<K, V> Iterator for MapEntries<K, V>
+where
+    K: FromValue,
+    V: FromValue,
+{
+    type Item = Result<(K, V)>;
+
+    /// This will return `Some(Err(...))` if the next entry's key or value failed to be converted
+    /// into `K` or `V` respectively (through `FromValue`).
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.entries.len() {
+            return None;
+        }
+
+        let key = self.entries.get(self.index);
+        let value = self.entries.get(self.index + 1);
+        self.index += 2;
+
+        match (key, value) {
+            (Ok(key), Ok(value)) => Some(Ok((key, value))),
+            (Err(e), _) | (_, Err(e)) => Some(Err(e)),
+        }
+    }
+}
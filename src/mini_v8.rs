@@ -1,24 +1,50 @@
 use crate::*;
+use crate::profiler::Profiler;
 use std::any::Any;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::BTreeMap;
-use std::rc::Rc;
+use std::ffi::c_void;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use std::rc::{Rc, Weak};
 use std::string::String as StdString;
 use std::sync::{Arc, Condvar, Mutex, Once};
 use std::thread;
 use std::time::Duration;
+use v8::ValueDeserializerHelper;
 
 #[derive(Clone)]
 pub struct MiniV8 {
     interface: Interface,
 }
 
+impl Default for MiniV8 {
+    fn default() -> MiniV8 {
+        MiniV8::new()
+    }
+}
+
 impl MiniV8 {
     pub fn new() -> MiniV8 {
+        MiniV8::with_params(Default::default())
+    }
+
+    /// Creates a new `MiniV8` whose `ArrayBuffer` memory is accounted for by the given allocator,
+    /// e.g. to cap the total size of buffers created by untrusted code. See `BufferAllocator`.
+    pub fn new_with_allocator(allocator: Box<dyn BufferAllocator>) -> MiniV8 {
+        let allocator = crate::allocator::create_allocator(allocator).make_shared();
+        MiniV8::with_params(v8::CreateParams::default().array_buffer_allocator(allocator))
+    }
+
+    fn with_params(params: v8::CreateParams) -> MiniV8 {
         initialize_v8();
-        let mut isolate = v8::Isolate::new(Default::default());
+        let mut isolate = v8::Isolate::new(params);
         initialize_slots(&mut isolate);
-        MiniV8 { interface: Interface::new(isolate) }
+        let mv8 = MiniV8 { interface: Interface::new(isolate) };
+        let callbacks = Callbacks::new(mv8.interface.downgrade());
+        mv8.scope(|scope| scope.set_slot(callbacks));
+        mv8
     }
 
     /// Returns the global JavaScript object.
@@ -32,6 +58,12 @@ impl MiniV8 {
         })
     }
 
+    /// Returns a handle for terminating JavaScript execution in this `MiniV8`, which can be sent to
+    /// and used from other threads (e.g. to cancel a long-running evaluation on some signal).
+    pub fn thread_safe_handle(&self) -> ThreadSafeHandle {
+        ThreadSafeHandle(self.interface.isolate_handle())
+    }
+
     /// Executes a JavaScript script and returns its result.
     pub fn eval<S, R>(&self, script: S) -> Result<R>
     where
@@ -39,47 +71,621 @@ impl MiniV8 {
         R: FromValue,
     {
         let script = script.into();
+        let timeout = script.timeout;
+        self.with_timeout(timeout, || self.eval_inner(script))?.into(self)
+    }
+
+    /// Executes a JavaScript script and extracts the values of the given keys from the global
+    /// object or from the script's result, as chosen by `source`. Missing keys are extracted as
+    /// `Value::Undefined`.
+    ///
+    /// Extracting from the result returns an error if the result is neither an object nor an
+    /// array. Note that top-level `let` and `const` declarations do not
+    /// create properties on the global object, and so can't be extracted from it.
+    pub fn eval_extract<S: Into<Script>>(
+        &self,
+        script: S,
+        keys: &[&str],
+        source: ExtractSource,
+    ) -> Result<Vec<Value>> {
+        let result: Value = self.eval(script)?;
+        let object = match (source, result) {
+            (ExtractSource::Global, _) => self.global(),
+            (ExtractSource::Result, Value::Array(array)) => array.into_object(),
+            (ExtractSource::Result, result) => result.into(self)?,
+        };
+        keys.iter().map(|key| object.get(*key)).collect()
+    }
+
+    /// Compiles a JavaScript script without running it, so that it can be run any number of times
+    /// with `CompiledScript::run` without being recompiled. The script's `timeout` applies to each
+    /// run, and its source is registered when it is compiled if `Script::register` is set.
+    pub fn compile<S: Into<Script>>(&self, script: S) -> Result<CompiledScript> {
+        let script = script.into();
+        self.try_catch(|scope| {
+            let compiled = compile_script(scope, &script);
+            self.exception(scope)?;
+            let compiled = compiled.unwrap();
+            if script.register {
+                register_script(scope, compiled, &script.source);
+            }
+            let unbound = compiled.get_unbound_script(scope);
+            Ok(CompiledScript {
+                mv8: self.clone(),
+                handle: v8::Global::new(scope, unbound),
+                timeout: script.timeout,
+                source_hash: source_hash(&script.source),
+            })
+        })
+    }
+
+    /// Compiles a JavaScript script like `MiniV8::compile`, using code cache data previously
+    /// created with `CompiledScript::create_code_cache` to skip most of the compilation work.
+    ///
+    /// `create_code_cache` prefixes the cache data with a header recording the script's source and
+    /// the version and configuration of V8. Cache data whose header doesn't match is never passed
+    /// to V8: the script is compiled from scratch and the returned `bool` is `true`.
+    ///
+    /// A `false` result only means that the cache data was passed to V8, not that V8 used it. If V8
+    /// itself rejects the data (e.g. because it is corrupt), it silently compiles the script from
+    /// scratch, and this cannot be detected.
+    pub fn compile_with_cache<S: Into<Script>>(
+        &self,
+        script: S,
+        cached_data: Vec<u8>,
+    ) -> Result<(CompiledScript, bool)> {
+        use v8::script_compiler::{self, CachedData, CompileOptions, NoCacheReason, Source};
+
+        let script = script.into();
+        let source_hash = source_hash(&script.source);
+        let header = code_cache_header(source_hash);
+        let cached_data = match cached_data.split_at(cached_data.len().min(header.len())) {
+            (prefix, data) if prefix == header && !data.is_empty() => Some(data),
+            _ => None,
+        };
+        self.try_catch(|scope| {
+            let source = create_string(scope, &script.source);
+            let origin = script.origin.as_ref().map(|o| create_script_origin(scope, o));
+            let origin = origin.as_ref();
+            let (source, options) = match cached_data {
+                Some(data) => {
+                    let data = CachedData::new(data);
+                    let source = Source::new_with_cached_data(source, origin, data);
+                    (source, CompileOptions::ConsumeCodeCache)
+                },
+                None => {
+                    let source = Source::new(source, origin);
+                    (source, CompileOptions::NoCompileOptions)
+                },
+            };
+            let unbound = script_compiler::compile_unbound_script(
+                scope,
+                source,
+                options,
+                NoCacheReason::NoReason,
+            );
+            self.exception(scope)?;
+            let unbound = unbound.unwrap();
+            if script.register {
+                let compiled = unbound.bind_to_current_context(scope);
+                register_script(scope, compiled, &script.source);
+            }
+            let compiled = CompiledScript {
+                mv8: self.clone(),
+                handle: v8::Global::new(scope, unbound),
+                timeout: script.timeout,
+                source_hash,
+            };
+            Ok((compiled, cached_data.is_none()))
+        })
+    }
+
+    // Runs `func`, terminating JavaScript execution if it runs longer than `timeout`.
+    fn with_timeout<F>(&self, timeout: Option<Duration>, func: F) -> Result<Value>
+    where
+        F: FnOnce() -> Result<Value>,
+    {
         let isolate_handle = self.interface.isolate_handle();
-        match (self.interface.len() == 1, script.timeout) {
+        match (self.interface.len() == 1, timeout) {
             (true, Some(timeout)) => {
                 execute_with_timeout(
                     timeout,
-                    || self.eval_inner(script),
+                    func,
                     move || { isolate_handle.terminate_execution(); },
-                )?.into(self)
+                )
             },
             (false, Some(_)) => Err(Error::InvalidTimeout),
-            (_, None) => self.eval_inner(script)?.into(self),
+            (_, None) => func(),
         }
     }
 
-    fn eval_inner(&self, script: Script) -> Result<Value> {
+    /// Compiles a JavaScript script without running it, to check whether its syntax is valid.
+    ///
+    /// Returns an `Error::Value` holding the JavaScript `SyntaxError` if the script is invalid.
+    pub fn check_syntax(&self, source: &str) -> Result<()> {
         self.try_catch(|scope| {
-            let source = create_string(scope, &script.source);
-            let origin = script.origin.map(|o| {
-                let name = create_string(scope, &o.name).into();
-                let source_map_url = create_string(scope, "").into();
-                v8::ScriptOrigin::new(
-                    scope,
-                    name,
-                    o.line_offset,
-                    o.column_offset,
-                    false,
-                    0,
-                    source_map_url,
-                    true,
-                    false,
-                    false,
+            let source = create_string(scope, source);
+            v8::Script::compile(scope, source, None);
+            self.exception(scope)
+        })
+    }
+
+    /// Executes a JavaScript script and returns its completion value, distinguishing scripts that
+    /// complete without a value (e.g. `let x = 1` or `function f() {}`) from scripts whose value is
+    /// `undefined`, which `MiniV8::eval` reports identically. This lets read-eval-print loops and
+    /// notebooks decide whether there's anything to display.
+    pub fn eval_completion<S: Into<Script>>(&self, script: S) -> Result<Completion> {
+        // Per JavaScript's completion semantics, a script that completes without a value takes
+        // the value of the statement preceding it, so the script is compiled preceded by a
+        // sentinel. The sentinel is a string literal, so it doesn't prevent a following
+        // `"use strict"` directive from taking effect, and it's unique to this call, so the
+        // script can't complete with it by accident:
+        let sentinel = format!("mini-v8:empty-completion:{:016x}", random_u64());
+        let prefix = format!("'{}';", sentinel);
+        let script = script.into();
+        let result = self.with_timeout(script.timeout, || self.try_catch(|scope| {
+            // A hashbang comment is only allowed at the very start of the script, so it's kept as
+            // the equivalent single-line comment:
+            let source = match script.source.strip_prefix("#!") {
+                Some(rest) => format!("{}//{}", prefix, rest),
+                None => format!("{}{}", prefix, script.source),
+            };
+            let source = create_string(scope, &source);
+            let origin = prefixed_script_origin(scope, script.origin.as_ref(), prefix.len());
+            let compiled = v8::Script::compile(scope, source, Some(&origin));
+            self.exception(scope)?;
+            let compiled = compiled.unwrap();
+            if script.register {
+                register_script(scope, compiled, &script.source);
+            }
+            let result = compiled.run(scope);
+            self.exception(scope)?;
+            Ok(Value::from_v8_value(self, scope, result.unwrap()))
+        }))?;
+        match result {
+            Value::String(ref string) if string.to_string() == sentinel => Ok(Completion::Empty),
+            value => Ok(Completion::Value(value)),
+        }
+    }
+
+    /// Evaluates JavaScript source with an indirect call to the original built-in `eval` function,
+    /// i.e. `(0, eval)(source)`, and returns its result.
+    ///
+    /// Like `MiniV8::eval`, indirect eval runs in the global scope with `this` set to the global
+    /// object, but the source is evaluated as eval code rather than as a script: `var` and
+    /// function declarations become deletable properties of the global object, `let`, `const` and
+    /// `class` declarations are scoped to the call and don't persist afterward, and the call fails
+    /// with a JavaScript `EvalError` where code generation from strings is disallowed (see
+    /// `ResourceLimits::allow_code_generation`).
+    pub fn indirect_eval(&self, source: &str) -> Result<Value> {
+        self.builtin(|builtins| &builtins.eval).call((source,))
+    }
+
+    /// Executes a JavaScript script and returns its result, converted strictly to the number type
+    /// `R`. Non-finite, fractional (for integer types), or out-of-range results return an error
+    /// instead of silently becoming some other number. `MiniV8::eval` already does this for
+    /// integer types, but not for `f32` or `f64`. See `Checked`.
+    pub fn eval_checked<S, R>(&self, script: S) -> Result<R>
+    where
+        S: Into<Script>,
+        Checked<R>: FromValue,
+    {
+        self.eval::<_, Checked<R>>(script).map(|checked| checked.0)
+    }
+
+    /// Executes a JavaScript script and returns its result as a finite number, for numeric
+    /// pipelines that can't tolerate `NaN` or infinite values. Returns an
+    /// `Error::FromJsConversionError` if the result coerces to `NaN` or an infinity. This is
+    /// shorthand for `MiniV8::eval_checked::<_, f64>`.
+    pub fn eval_finite<S: Into<Script>>(&self, script: S) -> Result<f64> {
+        self.eval_checked(script)
+    }
+
+    /// Executes a JavaScript script under the given resource limits and returns its result. This
+    /// is intended for running untrusted code: breaching any of the limits results in an error from
+    /// which the `MiniV8` can recover, rather than aborting the process.
+    ///
+    /// Exceeding the timeout returns `Error::Timeout` and exceeding the heap limit returns
+    /// `Error::HeapLimit`. Exceeding the stack limit raises a JavaScript `RangeError`, returned as
+    /// `Error::StackOverflow`, and attempting to compile code from strings while code generation
+    /// is disallowed raises a JavaScript `EvalError`, returned as `Error::Value`, unless the
+    /// script catches them. If both the script and `limits` specify a timeout, the shorter one
+    /// applies.
+    ///
+    /// Like timeouts, sandboxed evaluations cannot be nested: calling this from within a `Function`
+    /// created with `MiniV8::create_function` or `MiniV8::create_function_mut` returns
+    /// `Error::InvalidSandbox`.
+    pub fn eval_sandboxed<S, R>(&self, script: S, limits: ResourceLimits) -> Result<R>
+    where
+        S: Into<Script>,
+        R: FromValue,
+    {
+        if self.interface.len() != 1 {
+            return Err(Error::InvalidSandbox);
+        }
+
+        let mut script = script.into();
+        script.timeout = match (script.timeout, limits.timeout) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+
+        let heap_guard = limits.max_heap_size.map(|max_heap_size| Rc::new(HeapGuard {
+            isolate_handle: self.interface.isolate_handle(),
+            max_heap_size,
+            exceeded: Cell::new(false),
+            initial_heap_limit: Cell::new(0),
+        }));
+
+        let stack_base = stack_position();
+        let allowed_code_generation = self.scope(|scope| {
+            let context = scope.get_current_context();
+            let allowed = context.is_code_generation_from_strings_allowed();
+            context.set_allow_generation_from_strings(limits.allow_code_generation);
+            if let Some(max_stack_size) = limits.max_stack_size {
+                set_stack_limit(scope, stack_base.saturating_sub(max_stack_size));
+            }
+            if let Some(ref guard) = heap_guard {
+                let data = Rc::as_ptr(guard) as *mut c_void;
+                scope.add_near_heap_limit_callback(near_heap_limit_callback, data);
+                scope.set_slot(guard.clone());
+            }
+            allowed
+        });
+
+        let result = match heap_guard {
+            Some(_) => {
+                let isolate_handle = self.interface.isolate_handle();
+                execute_with_poll(
+                    HEAP_POLL_INTERVAL,
+                    || self.eval::<_, Value>(script),
+                    move || {
+                        isolate_handle.request_interrupt(heap_check_interrupt, ptr::null_mut());
+                    },
                 )
-            });
-            let script = v8::Script::compile(scope, source, origin.as_ref());
+            },
+            None => self.eval::<_, Value>(script),
+        };
+
+        self.scope(|scope| {
+            let context = scope.get_current_context();
+            context.set_allow_generation_from_strings(allowed_code_generation);
+            if limits.max_stack_size.is_some() {
+                // V8 can't report its previous stack limit, so V8's default stack size is granted
+                // again, measured from this call:
+                set_stack_limit(scope, stack_base.saturating_sub(DEFAULT_STACK_SIZE));
+            }
+            if let Some(ref guard) = heap_guard {
+                // This restores the heap limit if `near_heap_limit_callback` raised it (V8 ignores
+                // a limit of zero):
+                let heap_limit = guard.initial_heap_limit.get();
+                scope.remove_near_heap_limit_callback(near_heap_limit_callback, heap_limit);
+                scope.remove_slot::<Rc<HeapGuard>>();
+            }
+        });
+
+        if heap_guard.is_some_and(|guard| guard.exceeded.get()) {
+            return Err(Error::HeapLimit);
+        }
+
+        result?.into(self)
+    }
+
+    fn eval_inner(&self, script: Script) -> Result<Value> {
+        self.try_catch(|scope| {
+            let compiled = compile_script(scope, &script);
             self.exception(scope)?;
-            let result = script.unwrap().run(scope);
+            let compiled = compiled.unwrap();
+            if script.register {
+                register_script(scope, compiled, &script.source);
+            }
+            let result = compiled.run(scope);
             self.exception(scope)?;
             Ok(Value::from_v8_value(self, scope, result.unwrap()))
         })
     }
 
+    /// Returns the source of the script with the given id, if the script was evaluated with
+    /// `Script::register` set.
+    pub fn script_source(&self, id: usize) -> Option<StdString> {
+        self.interface.use_slot(|registry: &ScriptRegistry| registry.0.borrow().get(&id).cloned())
+    }
+
+    /// Returns the id of the script containing the innermost JavaScript stack frame, or `None` if
+    /// no JavaScript is running. This is typically called from within a Rust function called from
+    /// JavaScript, in order to look up the calling script with `MiniV8::script_source`.
+    pub fn current_script_id(&self) -> Option<usize> {
+        self.scope(|scope| {
+            let trace = v8::StackTrace::current_stack_trace(scope, 1)?;
+            let frame = trace.get_frame(scope, 0)?;
+            Some(frame.get_script_id())
+        })
+    }
+
+    /// Evaluates a JavaScript ES module and returns its namespace object, whose properties are the
+    /// module's exports. The module's `name` is used as its origin, and is passed to the hook set
+    /// with `MiniV8::set_import_meta`.
+    ///
+    /// Pending microtasks are run after evaluation, so top-level `await` on settled promises
+    /// completes before this returns. A module that throws (or whose top-level `await` rejects)
+    /// returns an `Error::Value` holding the thrown value.
+    ///
+    /// Successfully evaluated modules are cached by name, so that they are only evaluated once
+    /// and their state is shared, like Node's module cache: evaluating a module with the name of
+    /// a cached module returns the cached module's namespace and ignores `source`. Modules can
+    /// import cached modules using their names as specifiers; importing any other specifier fails
+    /// with an `Error::Value`. See `MiniV8::clear_module_cache`, and
+    /// `MiniV8::eval_module_with_resolver` for loading imported modules on demand.
+    pub fn eval_module(&self, name: &str, source: &str) -> Result<Object> {
+        self.eval_module_with_resolver(name, source, |specifier, _| {
+            if self.cached_module(specifier).is_some() {
+                Ok(ModuleSource { name: specifier.to_owned(), source: StdString::new() })
+            } else {
+                let message = format!(
+                    "cannot import {:?}: no module with that name is cached",
+                    specifier,
+                );
+                Err(Error::ExternalError(message.into()))
+            }
+        })
+    }
+
+    /// Evaluates a JavaScript ES module like `MiniV8::eval_module`, loading the modules it imports
+    /// (and the modules they import, and so on) with the given resolver.
+    ///
+    /// The resolver is called with each `import` specifier and the name of the importing module,
+    /// and returns the imported module's resolved name and source. Resolved names identify
+    /// modules: specifiers resolving to the same name import the same module, and modules with a
+    /// cached name (see `MiniV8::eval_module`) are reused without being evaluated again. Circular
+    /// imports are supported, as in JavaScript.
+    ///
+    /// Errors returned by the resolver are converted to JavaScript values (see `Error::to_value`)
+    /// and returned as an `Error::Value`. Imported modules are only added to the module cache if
+    /// the whole evaluation succeeds.
+    pub fn eval_module_with_resolver<F>(
+        &self,
+        name: &str,
+        source: &str,
+        resolve: F,
+    ) -> Result<Object>
+    where
+        F: Fn(&str, &str) -> Result<ModuleSource>,
+    {
+        if let Some(module) = self.cached_module(name) {
+            return Ok(self.module_namespace(&module));
+        }
+
+        let mut graph = ModuleGraph::default();
+        let root = match self.load_module(name, source, &resolve, &mut graph) {
+            Ok(root) => root,
+            Err(error) => {
+                self.forget_module_names(graph.modules.values());
+                return Err(error);
+            },
+        };
+        let ModuleGraph { modules, resolutions } = graph;
+
+        // The resolutions are only needed while the modules are being instantiated:
+        self.interface.use_slot(|r: &ModuleResolutions| *r.0.borrow_mut() = resolutions);
+        let result = self.try_catch(|scope| {
+            let module = v8::Local::new(scope, root.clone());
+            // Failures are reported as exceptions, which are checked below instead:
+            let _ = module.instantiate_module(scope, resolve_module);
+            self.exception(scope)?;
+            let _ = module.evaluate(scope);
+            self.exception(scope)?;
+            scope.perform_microtask_checkpoint();
+            if module.get_status() == v8::ModuleStatus::Errored {
+                let exception = module.get_exception();
+                return Err(Error::Value(Value::from_v8_value(self, scope, exception)));
+            }
+            Ok(())
+        });
+        self.interface.use_slot(|r: &ModuleResolutions| r.0.borrow_mut().clear());
+        if let Err(error) = result {
+            self.forget_module_names(modules.values());
+            return Err(error);
+        }
+
+        self.interface.use_slot(|cache: &ModuleCache| cache.0.borrow_mut().extend(modules));
+        Ok(self.module_namespace(&root))
+    }
+
+    // Compiles the module, and recursively resolves and compiles the modules it imports, adding
+    // them to `graph`. Modules that are cached or already in `graph` are not compiled again.
+    fn load_module<F>(
+        &self,
+        name: &str,
+        source: &str,
+        resolve: &F,
+        graph: &mut ModuleGraph,
+    ) -> Result<v8::Global<v8::Module>>
+    where
+        F: Fn(&str, &str) -> Result<ModuleSource>,
+    {
+        let (module, specifiers) = self.compile_module(name, source)?;
+        graph.modules.insert(name.to_owned(), module.clone());
+
+        for specifier in specifiers {
+            let resolved = resolve(&specifier, name).map_err(|e| Error::Value(e.to_value(self)))?;
+            let loaded = graph.modules.get(&resolved.name).cloned();
+            let target = match loaded.or_else(|| self.cached_module(&resolved.name)) {
+                Some(target) => target,
+                None => self.load_module(&resolved.name, &resolved.source, resolve, graph)?,
+            };
+            graph.resolutions.insert((name.to_owned(), specifier), target);
+        }
+
+        Ok(module)
+    }
+
+    // Compiles a module, returning it along with the specifiers of the modules it imports.
+    fn compile_module(
+        &self,
+        name: &str,
+        source: &str,
+    ) -> Result<(v8::Global<v8::Module>, Vec<StdString>)> {
+        self.try_catch(|scope| {
+            let source = create_string(scope, source);
+            let resource_name = create_string(scope, name).into();
+            let source_map_url = create_string(scope, "").into();
+            let origin = v8::ScriptOrigin::new(
+                scope,
+                resource_name,
+                0,
+                0,
+                false,
+                0,
+                source_map_url,
+                false,
+                false,
+                true,
+            );
+            let source = v8::script_compiler::Source::new(source, Some(&origin));
+            let module = v8::script_compiler::compile_module(scope, source);
+            self.exception(scope)?;
+            let module = module.unwrap();
+            let handle = v8::Global::new(scope, module);
+            let hash = module.get_identity_hash().get();
+            let names = scope.get_slot::<ModuleNames>().unwrap();
+            let mut names = names.0.borrow_mut();
+            names.entry(hash).or_default().push((handle.clone(), name.to_owned()));
+            drop(names);
+
+            let requests = module.get_module_requests();
+            let specifiers = (0..requests.length()).map(|i| {
+                let request = requests.get(scope, i).unwrap();
+                let request = v8::Local::<v8::ModuleRequest>::try_from(request).unwrap();
+                request.get_specifier().to_rust_string_lossy(scope)
+            }).collect();
+            Ok((handle, specifiers))
+        })
+    }
+
+    // Removes the given modules from the names used to identify modules in callbacks, so that the
+    // modules can be garbage collected.
+    fn forget_module_names<'a, I>(&self, modules: I)
+    where
+        I: IntoIterator<Item = &'a v8::Global<v8::Module>>,
+    {
+        self.scope(|scope| {
+            for module in modules {
+                let module = v8::Local::new(scope, module.clone());
+                let hash = module.get_identity_hash().get();
+                let names = scope.get_slot::<ModuleNames>().unwrap();
+                let mut names = names.0.borrow_mut();
+                if let Some(bucket) = names.get_mut(&hash) {
+                    bucket.retain(|(handle, _)| module != *handle);
+                    if bucket.is_empty() {
+                        names.remove(&hash);
+                    }
+                }
+            }
+        });
+    }
+
+    // Returns the number of modules whose names are kept for identifying them in callbacks.
+    #[cfg(test)]
+    pub(crate) fn module_name_count(&self) -> usize {
+        self.interface.use_slot(|names: &ModuleNames| names.0.borrow().values().map(Vec::len).sum())
+    }
+
+    fn cached_module(&self, name: &str) -> Option<v8::Global<v8::Module>> {
+        self.interface.use_slot(|cache: &ModuleCache| cache.0.borrow().get(name).cloned())
+    }
+
+    fn module_namespace(&self, module: &v8::Global<v8::Module>) -> Object {
+        self.scope(|scope| {
+            let module = v8::Local::new(scope, module.clone());
+            let namespace = v8::Local::<v8::Object>::try_from(module.get_module_namespace());
+            Object { mv8: self.clone(), handle: v8::Global::new(scope, namespace.unwrap()) }
+        })
+    }
+
+    /// Clears the cache of modules evaluated with `MiniV8::eval_module`. Modules evaluated
+    /// afterward are evaluated anew, even if a module with the same name was evaluated before, and
+    /// can no longer import the previously cached modules.
+    pub fn clear_module_cache(&self) {
+        let modules = self.interface.use_slot(|cache: &ModuleCache| cache.0.take());
+        self.forget_module_names(modules.values());
+    }
+
+    /// Installs a `console` object on the global object whose `log`, `info`, `warn` and `error`
+    /// methods forward their level and arguments to the given sink, replacing any existing
+    /// `console`. The sink receives the arguments as-is, so that callers can format them however
+    /// they like.
+    ///
+    /// Returns an error if the global object does not allow `console` to be set.
+    pub fn set_console<F>(&self, sink: F) -> Result<()>
+    where
+        F: Fn(ConsoleLevel, Values) + 'static,
+    {
+        let sink = Rc::new(sink);
+        let console = self.create_object();
+        let methods = [
+            ("log", ConsoleLevel::Log),
+            ("info", ConsoleLevel::Info),
+            ("warn", ConsoleLevel::Warn),
+            ("error", ConsoleLevel::Error),
+        ];
+        for &(name, level) in methods.iter() {
+            let sink = sink.clone();
+            console.set(name, self.create_function(move |invocation| {
+                sink(level, invocation.args);
+                Ok(())
+            }))?;
+        }
+        self.global().set("console", console)
+    }
+
+    /// Sets a hook that populates the `import.meta` object of each ES module evaluated with
+    /// `MiniV8::eval_module`, e.g. to set `import.meta.url`. The hook receives the module's name
+    /// and its `import.meta` object, and is called the first time a module accesses
+    /// `import.meta`. Setting a new hook replaces the previous one.
+    pub fn set_import_meta<F>(&self, hook: F)
+    where
+        F: Fn(&str, &Object) + 'static,
+    {
+        let import_meta = ImportMeta { interface: self.interface.downgrade(), hook: Rc::new(hook) };
+        self.scope(|scope| {
+            scope.set_slot(import_meta);
+            scope.set_host_initialize_import_meta_object_callback(initialize_import_meta);
+        });
+    }
+
+    /// Limits how deeply calls to Rust functions created with `MiniV8::create_function` (and its
+    /// variants) can nest, e.g. through Rust callbacks that call JavaScript that calls back into
+    /// Rust. A call that would exceed the limit throws a JavaScript exception for
+    /// `Error::MaxDepthExceeded` instead of calling the Rust function, so that runaway recursion
+    /// fails cleanly rather than overflowing the stack. Pass `None` to remove the limit, which is
+    /// the default.
+    pub fn set_max_callback_depth(&self, max: Option<usize>) {
+        self.interface.use_slot(|depth: &CallbackDepth| depth.max.set(max));
+    }
+
+    /// Returns details about the most recent JavaScript exception returned as an error by this
+    /// `MiniV8` (e.g. as an `Error::Value`), such as where it was thrown, or `None` if no
+    /// exception has been returned yet. This saves parsing the exception's stack trace to locate
+    /// it.
+    pub fn last_exception_details(&self) -> Option<ExceptionDetails> {
+        self.interface.use_slot(|last: &LastException| last.0.borrow().clone())
+    }
+
+    // Runs `func` without letting the exceptions it catches and discards (rather than returning
+    // them to the caller) change `MiniV8::last_exception_details`.
+    pub(crate) fn preserving_last_exception<T>(&self, func: impl FnOnce() -> T) -> T {
+        let last = self.last_exception_details();
+        let result = func();
+        self.interface.use_slot(|slot: &LastException| *slot.0.borrow_mut() = last);
+        result
+    }
+
+    /// Runs all pending microtasks, such as the reactions to settled promises, until the
+    /// microtask queue is empty.
+    pub fn run_microtasks(&self) {
+        self.scope(|scope| scope.perform_microtask_checkpoint());
+    }
+
     /// Inserts any sort of keyed value of type `T` into the `MiniV8`, typically for later retrieval
     /// from within Rust functions called from within JavaScript. If a value already exists with the
     /// key, it is returned.
@@ -91,6 +697,27 @@ impl MiniV8 {
         self.interface.use_slot(|m: &AnyMap| m.0.borrow_mut().insert(key.to_string(), Box::new(data)))
     }
 
+    /// Inserts a keyed value of type `T` like `MiniV8::set_user_data`, but returns the previous
+    /// value with the key as a `T`. Returns `None` if no value existed with the key, or if the
+    /// previous value was not of type `T` (in which case it is dropped).
+    pub fn set_user_data_typed<K, T>(&self, key: K, data: T) -> Option<T>
+    where
+        K: ToString,
+        T: Any,
+    {
+        let previous = self.set_user_data(key, data)?;
+        previous.downcast::<T>().ok().map(|previous| *previous)
+    }
+
+    /// Returns a clone of a user data value by its key, or `None` if no value exists with the key
+    /// or the value is not of type `T`. Unlike `MiniV8::use_user_data`, this makes it easy to keep
+    /// the value around, which is inexpensive for values like `Rc`s.
+    pub fn user_data_clone<T: Any + Clone>(&self, key: &str) -> Option<T> {
+        self.interface.use_slot(|m: &AnyMap| {
+            m.0.borrow().get(key).and_then(|d| d.downcast_ref::<T>()).cloned()
+        })
+    }
+
     /// Calls a function with a user data value by its key, or `None` if no value exists with the
     /// key. If a value exists but it is not of the type `T`, `None` is returned. This is typically
     /// used by a Rust function called from within JavaScript.
@@ -120,6 +747,90 @@ impl MiniV8 {
             String {
                 mv8: self.clone(),
                 handle: v8::Global::new(scope, string),
+                cache: Default::default(),
+            }
+        })
+    }
+
+    /// Creates and returns an internalized string managed by V8. V8 keeps a single copy of each
+    /// internalized string, so creating the same string again reuses it, and internalized strings
+    /// can be compared and looked up as property keys faster than other strings. This is useful
+    /// for strings that are used repeatedly as property keys.
+    ///
+    /// This is unrelated to the Rust-side caching of `String::as_cached_str`, which only avoids
+    /// repeatedly decoding a V8 string into Rust.
+    ///
+    /// # Panics
+    ///
+    /// Panics if source value is longer than `(1 << 28) - 16` bytes.
+    pub fn create_internalized_string(&self, value: &str) -> String {
+        self.scope(|scope| {
+            let string = v8::String::new_from_utf8(
+                scope,
+                value.as_bytes(),
+                v8::NewStringType::Internalized,
+            ).expect("string exceeds maximum length");
+            String {
+                mv8: self.clone(),
+                handle: v8::Global::new(scope, string),
+                cache: Default::default(),
+            }
+        })
+    }
+
+    /// Creates and returns a new, unique symbol with an optional description.
+    pub fn create_symbol(&self, description: Option<&str>) -> Symbol {
+        self.scope(|scope| {
+            let description = description.map(|d| create_string(scope, d));
+            let symbol = v8::Symbol::new(scope, description);
+            Symbol {
+                mv8: self.clone(),
+                handle: v8::Global::new(scope, symbol),
+            }
+        })
+    }
+
+    /// Returns the symbol in the global symbol registry with the given key, creating it if it
+    /// does not yet exist. This is equivalent to JavaScript's `Symbol.for(key)`.
+    pub fn symbol_for(&self, key: &str) -> Symbol {
+        self.scope(|scope| {
+            let key = create_string(scope, key);
+            let symbol = v8::Symbol::for_global(scope, key);
+            Symbol {
+                mv8: self.clone(),
+                handle: v8::Global::new(scope, symbol),
+            }
+        })
+    }
+
+    /// Parses a JSON string into a JavaScript value, as JavaScript's `JSON.parse` would.
+    ///
+    /// Returns an `Error::Value` holding a JavaScript `SyntaxError` if the string is not valid
+    /// JSON.
+    pub fn parse_json(&self, json: &str) -> Result<Value> {
+        self.try_catch(|scope| {
+            let json = create_string(scope, json);
+            let result = v8::json::parse(scope, json);
+            self.exception(scope)?;
+            Ok(Value::from_v8_value(self, scope, result.unwrap()))
+        })
+    }
+
+    /// Deserializes a value serialized with `Value::serialize`, possibly by another `MiniV8`.
+    ///
+    /// Returns an error if the data is not a valid serialized value.
+    pub fn deserialize(&self, data: &[u8]) -> Result<Value> {
+        self.try_catch(|scope| {
+            let context = scope.get_current_context();
+            let mut deserializer = v8::ValueDeserializer::new(scope, Box::new(Deserializer), data);
+            let value = match deserializer.read_header(context) {
+                Some(true) => deserializer.read_value(context),
+                _ => None,
+            };
+            self.exception(scope)?;
+            match value {
+                Some(value) => Ok(Value::from_v8_value(self, scope, value)),
+                None => Err(Error::ExternalError("invalid serialized value".into())),
             }
         })
     }
@@ -135,6 +846,152 @@ impl MiniV8 {
         })
     }
 
+    /// Creates and returns an `Array` managed by V8 filled with the values from an iterator.
+    ///
+    /// Unlike calling `Array::push` repeatedly, this creates the array at its final size in a
+    /// single step. Returns the first error from `ToValue::to_value` for the values, if any.
+    pub fn create_array_from<V, I>(&self, iter: I) -> Result<Array>
+    where
+        V: ToValue,
+        I: IntoIterator<Item = V>,
+    {
+        let values = iter.into_iter()
+            .map(|v| v.to_value(self))
+            .collect::<Result<Vec<Value>>>()?;
+        Ok(self.scope(|scope| {
+            let elements = values.iter().map(|v| v.to_v8_value(scope)).collect::<Vec<_>>();
+            let array = v8::Array::new_with_elements(scope, &elements);
+            Array { mv8: self.clone(), handle: v8::Global::new(scope, array) }
+        }))
+    }
+
+    /// Creates and returns an `Array` managed by V8 filled with the values from a fallible
+    /// iterator. Iteration stops at the first `Err`, which is returned instead of the array.
+    ///
+    /// This is a thin wrapper around `MiniV8::create_array` and `Array::push`. See `Array::push`
+    /// for how this method might otherwise return an error.
+    pub fn try_create_array_from<V, I>(&self, iter: I) -> Result<Array>
+    where
+        V: ToValue,
+        I: IntoIterator<Item = Result<V>>,
+    {
+        let array = self.create_array();
+        for v in iter {
+            array.push(v?)?;
+        }
+        Ok(array)
+    }
+
+    /// Creates and returns an `ArrayBuffer` managed by V8 that takes ownership of the given bytes,
+    /// without copying them.
+    pub fn create_array_buffer_from(&self, data: Vec<u8>) -> ArrayBuffer {
+        self.scope(|scope| {
+            let backing_store = v8::ArrayBuffer::new_backing_store_from_vec(data).make_shared();
+            let buffer = v8::ArrayBuffer::with_backing_store(scope, &backing_store);
+            ArrayBuffer {
+                mv8: self.clone(),
+                handle: v8::Global::new(scope, buffer),
+            }
+        })
+    }
+
+    /// Creates and returns a `Uint8Array` viewing `len` bytes of the given buffer, starting at
+    /// `offset`.
+    ///
+    /// Returns `Error::OutOfBounds` if the view would extend past the end of the buffer.
+    pub fn create_uint8_array(
+        &self,
+        buffer: &ArrayBuffer,
+        offset: usize,
+        len: usize,
+    ) -> Result<TypedArray> {
+        let buffer_len = buffer.byte_length();
+        let end = offset.saturating_add(len);
+        if end > buffer_len {
+            return Err(Error::OutOfBounds { index: end, len: buffer_len });
+        }
+
+        Ok(self.scope(|scope| {
+            let buffer = v8::Local::new(scope, buffer.handle.clone());
+            let array: v8::Local<v8::TypedArray> =
+                v8::Uint8Array::new(scope, buffer, offset, len).unwrap().into();
+            TypedArray {
+                mv8: self.clone(),
+                handle: v8::Global::new(scope, array),
+            }
+        }))
+    }
+
+    /// Creates and returns a `Uint8Array` holding a copy of the given bytes.
+    pub fn create_uint8_array_from(&self, data: &[u8]) -> TypedArray {
+        let buffer = self.create_array_buffer_from(data.to_vec());
+        self.create_uint8_array(&buffer, 0, data.len()).unwrap()
+    }
+
+    /// Creates and returns an empty `Map` managed by V8.
+    pub fn create_map(&self) -> Map {
+        self.scope(|scope| {
+            let map = v8::Map::new(scope);
+            Map {
+                mv8: self.clone(),
+                handle: v8::Global::new(scope, map),
+            }
+        })
+    }
+
+    /// Creates and returns a regular expression with the given pattern and flags.
+    ///
+    /// Returns an `Error::Value` holding a JavaScript `SyntaxError` if the pattern is invalid.
+    pub fn create_regexp(&self, pattern: &str, flags: RegExpFlags) -> Result<RegExp> {
+        let constructor = self.builtin(|builtins| &builtins.regexp);
+        constructor.call_new((pattern, flags.to_flags_string()))
+    }
+
+    /// Creates and returns an empty `Set` managed by V8.
+    pub fn create_set(&self) -> Set {
+        self.scope(|scope| {
+            let set = v8::Set::new(scope);
+            Set {
+                mv8: self.clone(),
+                handle: v8::Global::new(scope, set),
+            }
+        })
+    }
+
+    /// Creates and returns an empty `WeakMap` managed by V8.
+    pub fn create_weak_map(&self) -> WeakMap {
+        let constructor = self.builtin(|builtins| &builtins.weak_map);
+        match constructor.call_new(()) {
+            Ok(Value::WeakMap(weak_map)) => weak_map,
+            _ => unreachable!("`WeakMap` constructor did not return a `WeakMap`"),
+        }
+    }
+
+    /// Creates and returns an empty `WeakSet` managed by V8.
+    pub fn create_weak_set(&self) -> WeakSet {
+        let constructor = self.builtin(|builtins| &builtins.weak_set);
+        match constructor.call_new(()) {
+            Ok(Value::WeakSet(weak_set)) => weak_set,
+            _ => unreachable!("`WeakSet` constructor did not return a `WeakSet`"),
+        }
+    }
+
+    /// Creates and returns a `Set` managed by V8 filled with the values from an iterator.
+    ///
+    /// This is a thin wrapper around `MiniV8::create_set` and `Set::add`. See `Set::add` for how
+    /// this method might return an error.
+    pub fn create_set_from<V, I>(&self, iter: I) -> Result<Set>
+    where
+        V: ToValue,
+        I: IntoIterator<Item = V>,
+    {
+        let set = self.create_set();
+        for v in iter {
+            set.add(v)?;
+        }
+        Ok(set)
+    }
+
     /// Creates and returns an empty `Object` managed by V8.
     pub fn create_object(&self) -> Object {
         self.scope(|scope| {
@@ -146,11 +1003,48 @@ impl MiniV8 {
         })
     }
 
+    /// Creates and returns an empty `Object` managed by V8 with the given number of internal
+    /// fields, for storing Rust data with `Object::set_internal_field`.
+    pub fn create_object_with_internal_fields(&self, count: usize) -> Object {
+        self.scope(|scope| {
+            let template = v8::ObjectTemplate::new(scope);
+            template.set_internal_field_count(count);
+            let object = template.new_instance(scope).unwrap();
+            Object {
+                mv8: self.clone(),
+                handle: v8::Global::new(scope, object),
+            }
+        })
+    }
+
+    /// Creates and returns an `Object` managed by V8 with the given prototype, as JavaScript's
+    /// `Object.create` would. Pass `Value::Null` to create an object without a prototype.
+    ///
+    /// Returns an error in the same cases as `Object::set_prototype`.
+    pub fn create_object_with_prototype(&self, prototype: Value) -> Result<Object> {
+        let object = self.create_object();
+        object.set_prototype(prototype)?;
+        Ok(object)
+    }
+
+    /// Creates and returns an `Object` managed by V8 with the own enumerable properties of the
+    /// given objects copied onto it in order, so that later objects' properties take precedence,
+    /// like JavaScript's `Object.assign({}, ...objects)`. See `Object::assign` for how this method
+    /// might fail.
+    pub fn create_object_merged(&self, objects: &[Object]) -> Result<Object> {
+        let object = self.create_object();
+        for source in objects {
+            object.assign(source)?;
+        }
+        Ok(object)
+    }
+
     /// Creates and returns an `Object` managed by V8 filled with the keys and values from an
     /// iterator. Keys are coerced to object properties.
     ///
     /// This is a thin wrapper around `MiniV8::create_object` and `Object::set`. See `Object::set`
-    /// for how this method might return an error.
+    /// for how this method might fail; any such error is wrapped in an `Error::PropertyError`
+    /// identifying the offending property.
     pub fn create_object_from<K, V, I>(&self, iter: I) -> Result<Object>
     where
         K: ToValue,
@@ -158,12 +1052,57 @@ impl MiniV8 {
         I: IntoIterator<Item = (K, V)>,
     {
         let object = self.create_object();
-        for (k, v) in iter {
-            object.set(k, v)?;
+        for (index, (k, v)) in iter.into_iter().enumerate() {
+            let key = k.to_value(self).map_err(|error| Error::PropertyError {
+                index,
+                key: None,
+                source: Box::new(error),
+            })?;
+            if let Err(error) = object.set(key.clone(), v) {
+                let key = self.preserving_last_exception(|| key.coerce_string(self).ok());
+                let key = key.map(|key| key.to_string());
+                return Err(Error::PropertyError { index, key, source: Box::new(error) });
+            }
         }
         Ok(object)
     }
 
+    /// Formats a number using fixed-point notation with the given number of digits after the
+    /// decimal point, exactly as JavaScript's `Number.prototype.toFixed` would (including its
+    /// rounding behavior, e.g. `1.005` formats as `"1.00"`).
+    ///
+    /// This uses the original built-in function captured when the `MiniV8` was created, so it is
+    /// unaffected by scripts that replace `Number.prototype.toFixed`. Returns an error if `digits`
+    /// is out of the range accepted by JavaScript (0 to 100).
+    pub fn to_fixed(&self, value: f64, digits: usize) -> Result<StdString> {
+        self.format_number(value, digits, |builtins| &builtins.to_fixed)
+    }
+
+    /// Formats a number to the given number of significant digits, exactly as JavaScript's
+    /// `Number.prototype.toPrecision` would.
+    ///
+    /// Like `MiniV8::to_fixed`, this uses the original built-in function. Returns an error if
+    /// `precision` is out of the range accepted by JavaScript (1 to 100).
+    pub fn to_precision(&self, value: f64, precision: usize) -> Result<StdString> {
+        self.format_number(value, precision, |builtins| &builtins.to_precision)
+    }
+
+    fn format_number<F>(&self, value: f64, digits: usize, select: F) -> Result<StdString>
+    where
+        F: FnOnce(&Builtins) -> &v8::Global<v8::Function>,
+    {
+        self.builtin(select).call_method(value, (digits,))
+    }
+
+    // Returns one of the built-in functions captured when the `MiniV8` was created.
+    pub(crate) fn builtin<F>(&self, select: F) -> Function
+    where
+        F: FnOnce(&Builtins) -> &v8::Global<v8::Function>,
+    {
+        let handle = self.interface.use_slot(|builtins: &Builtins| select(builtins).clone());
+        Function { mv8: self.clone(), handle }
+    }
+
     /// Wraps a Rust function or closure, creating a callable JavaScript function handle to it.
     ///
     /// The function's return value is always a `Result`: If the function returns `Err`, the error
@@ -174,32 +1113,47 @@ impl MiniV8 {
     /// If the function returns `Ok`, the contained value will be converted to a JavaScript value.
     /// For details on Rust-to-JavaScript conversions, refer to the `ToValue` and `ToValues` traits.
     ///
-    /// If the provided function panics, the executable will be aborted.
+    /// If the provided function panics, the panic is caught and raised as a JavaScript exception
+    /// (an `Error` whose message includes the panic message, if it is a string), as if the
+    /// function had returned an error. The panic is still reported by the panic hook as usual.
     pub fn create_function<F, R>(&self, func: F) -> Function
     where
         F: Fn(Invocation) -> Result<R> + 'static,
         R: ToValue,
     {
-        let func = move |mv8: &MiniV8, this: Value, args: Values| {
-            func(Invocation { mv8: mv8.clone(), this, args })?.to_value(mv8)
+        let func = move |
+            mv8: &MiniV8,
+            this: Value,
+            args: Values,
+            is_construct_call: bool,
+            new_target: Value,
+        | {
+            let invocation = Invocation {
+                mv8: mv8.clone(),
+                this,
+                args,
+                is_construct_call,
+                new_target,
+            };
+            func(invocation)?.to_value(mv8)
         };
 
         self.scope(|scope| {
-            let callback = Box::new(func);
-            let callback_info = CallbackInfo { mv8: self.clone(), callback };
-            let ptr = Box::into_raw(Box::new(callback_info));
-            let ext = v8::External::new(scope, ptr as _);
+            let callbacks = scope.get_slot::<Callbacks>().unwrap().clone();
+            let index = callbacks.insert(Rc::new(func));
+            let data = v8::Integer::new_from_unsigned(scope, index);
 
             let v8_func = |
                 scope: &mut v8::HandleScope,
                 fca: v8::FunctionCallbackArguments,
                 mut rv: v8::ReturnValue,
             | {
-                let data = fca.data();
-                let ext = v8::Local::<v8::External>::try_from(data).unwrap();
-                let callback_info_ptr = ext.value() as *mut CallbackInfo;
-                let callback_info = unsafe { &mut *callback_info_ptr };
-                let CallbackInfo { mv8, callback } = callback_info;
+                let data = v8::Local::<v8::Integer>::try_from(fca.data()).unwrap();
+                let callbacks = scope.get_slot::<Callbacks>().unwrap().clone();
+                let (mv8, callback) = match callbacks.get(data.value() as u32) {
+                    Some(entry) => entry,
+                    None => return,
+                };
                 let ptr = scope as *mut v8::HandleScope;
                 // We can erase the lifetime of the `v8::HandleScope` safely because it only lives
                 // on the interface stack during the current block:
@@ -211,7 +1165,22 @@ impl MiniV8 {
                 for i in 0..len {
                     args.push(Value::from_v8_value(&mv8, scope, fca.get(i)));
                 }
-                match callback(&mv8, this, Values::from_vec(args)) {
+                let args = Values::from_vec(args);
+                // As in V8's `FunctionCallbackInfo::IsConstructCall`, which the `v8` crate doesn't
+                // bind:
+                let is_construct_call = !fca.new_target().is_undefined();
+                let new_target = Value::from_v8_value(&mv8, scope, fca.new_target());
+                let result = if mv8.interface.use_slot(CallbackDepth::enter) {
+                    // Unwinding out of this function into V8 would be undefined behavior:
+                    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                        callback(&mv8, this, args, is_construct_call, new_target)
+                    }));
+                    mv8.interface.use_slot(CallbackDepth::exit);
+                    result.unwrap_or_else(|payload| Err(panic_error(payload)))
+                } else {
+                    Err(Error::MaxDepthExceeded)
+                };
+                match result {
                     Ok(v) => {
                         rv.set(v.to_v8_value(scope));
                     },
@@ -223,17 +1192,12 @@ impl MiniV8 {
                 mv8.interface.pop();
             };
 
-            let value = v8::Function::builder(v8_func).data(ext.into()).build(scope).unwrap();
+            let value = v8::Function::builder(v8_func).data(data.into()).build(scope).unwrap();
             // TODO: `v8::Isolate::adjust_amount_of_external_allocated_memory` should be called
-            // appropriately with the following external resource size calculation. This cannot be
-            // done as of now, since `v8::Weak::with_guaranteed_finalizer` does not provide a
-            // `v8::Isolate` to the finalizer callback, and so the downward adjustment cannot be
-            // made.
-            //
-            // let func_size = mem::size_of_val(&func); let ext_size = func_size +
-            // mem::size_of::<CallbackInfo>;
-            let drop_ext = Box::new(move || drop(unsafe { Box::from_raw(ptr) }));
-            add_finalizer(scope, value, drop_ext);
+            // appropriately with the size of the callback. This cannot be done as of now, since
+            // `v8::Weak::with_guaranteed_finalizer` does not provide a `v8::Isolate` to the
+            // finalizer callback, and so the downward adjustment cannot be made.
+            add_finalizer(scope, value, move || callbacks.remove(index));
             Function {
                 mv8: self.clone(),
                 handle: v8::Global::new(scope, value),
@@ -241,6 +1205,115 @@ impl MiniV8 {
         })
     }
 
+    // Returns the number of Rust functions created with `MiniV8::create_function` that have not
+    // yet been garbage collected.
+    #[cfg(test)]
+    pub(crate) fn callback_count(&self) -> usize {
+        self.interface.use_slot(|callbacks: &Callbacks| callbacks.len())
+    }
+
+    /// Wraps a fallible Rust function or closure, creating a JavaScript function that always
+    /// returns a promise: the promise is fulfilled with the function's result if it returns `Ok`,
+    /// or rejected with the error's JavaScript value (see `Error::to_value`) if it returns `Err`.
+    /// Unlike with `MiniV8::create_function`, errors therefore surface through `await` and
+    /// `catch` handlers rather than as synchronous exceptions.
+    ///
+    /// The function itself still runs synchronously when called. Refer to `create_function` for
+    /// more information about the implementation.
+    pub fn create_async_function<F, R>(&self, func: F) -> Function
+    where
+        F: Fn(Invocation) -> Result<R> + 'static,
+        R: ToValue,
+    {
+        self.create_function(move |invocation| {
+            let mv8 = invocation.mv8.clone();
+            let result = func(invocation).and_then(|value| value.to_value(&mv8));
+            Ok(mv8.create_settled_promise(result))
+        })
+    }
+
+    /// Creates a JavaScript class backed by the given functions, returning its constructor. Each
+    /// method is installed on the class's prototype (non-enumerably, as with `class` syntax), so
+    /// instances created with `new` can call it with themselves as `this`. If a constructor is
+    /// given, it is called with the new instance as `this` to initialize it. As in JavaScript, if
+    /// the constructor returns an object, `new` evaluates to that object instead of the new
+    /// instance. As with `class` syntax, calling the class without `new` throws a `TypeError`.
+    ///
+    /// Returns an error if a method could not be installed.
+    pub fn create_class(
+        &self,
+        name: &str,
+        methods: Vec<(&str, Function)>,
+        constructor: Option<Function>,
+    ) -> Result<Function> {
+        let message = format!("class constructor {} cannot be invoked without 'new'", name);
+        let class = self.create_function(move |invocation| {
+            if !invocation.is_construct_call {
+                let mv8 = &invocation.mv8;
+                let error = mv8.scope(|scope| {
+                    let message = create_string(scope, &message);
+                    let error = v8::Exception::type_error(scope, message);
+                    Value::from_v8_value(mv8, scope, error)
+                });
+                return Err(Error::Value(error));
+            }
+            if let Some(constructor) = &constructor {
+                let this = invocation.this.clone();
+                let result: Value = constructor.call_method(this, invocation.args)?;
+                let is_object = invocation.mv8.scope(|scope| result.to_v8_value(scope).is_object());
+                if is_object {
+                    return Ok(result);
+                }
+            }
+            Ok(invocation.this)
+        });
+
+        let hidden = |value: Value| PropertyDescriptor {
+            value,
+            writable: true,
+            enumerable: false,
+            configurable: true,
+        };
+        let class_object = class.clone().into_object();
+        class_object.define_property("name", PropertyDescriptor {
+            writable: false,
+            ..hidden(Value::String(self.create_string(name)))
+        })?;
+        let prototype = self.create_object();
+        prototype.define_property("constructor", hidden(Value::Function(class.clone())))?;
+        for (name, method) in methods {
+            prototype.define_property(name, hidden(Value::Function(method)))?;
+        }
+        class_object.define_property("prototype", PropertyDescriptor {
+            writable: false,
+            configurable: false,
+            ..hidden(Value::Object(prototype))
+        })?;
+        Ok(class)
+    }
+
+    // Creates a promise that is already fulfilled with the value or rejected with the error.
+    fn create_settled_promise(&self, result: Result<Value>) -> Promise {
+        let (value, fulfilled) = match result {
+            Ok(value) => (value, true),
+            Err(error) => (error.to_value(self), false),
+        };
+        self.scope(|scope| {
+            let resolver = v8::PromiseResolver::new(scope).unwrap();
+            let value = value.to_v8_value(scope);
+            if fulfilled {
+                resolver.resolve(scope, value);
+            } else {
+                resolver.reject(scope, value);
+            }
+            let promise = resolver.get_promise(scope);
+            Promise {
+                mv8: self.clone(),
+                handle: v8::Global::new(scope, promise),
+            }
+        })
+    }
+
     /// Wraps a mutable Rust closure, creating a callable JavaScript function handle to it.
     ///
     /// This is a version of `create_function` that accepts a FnMut argument. Refer to
@@ -252,10 +1325,125 @@ impl MiniV8 {
     {
         let func = RefCell::new(func);
         self.create_function(move |invocation| {
-            (&mut *func.try_borrow_mut().map_err(|_| Error::RecursiveMutCallback)?)(invocation)
+            (*func.try_borrow_mut().map_err(|_| Error::RecursiveMutCallback)?)(invocation)
+        })
+    }
+
+    /// Wraps a Rust function or closure that belongs to an owner value, without keeping the owner
+    /// alive. This is useful for registering Rust callbacks as event handlers: a handler that
+    /// captures its owner would otherwise keep the owner from ever being garbage collected.
+    ///
+    /// On each call, the function receives the owner, or `None` if the owner has since been
+    /// garbage collected (in which case the function will typically do nothing). Refer to
+    /// `create_function` for more information about the implementation.
+    pub fn create_function_weak<F, R>(&self, weak_owner: WeakValue, func: F) -> Function
+    where
+        F: Fn(Option<Value>, Invocation) -> Result<R> + 'static,
+        R: ToValue,
+    {
+        self.create_function(move |invocation| func(weak_owner.upgrade(), invocation))
+    }
+
+    /// Wraps a Rust function or closure like `create_function`, passing the resulting function's
+    /// own handle into each call. This is useful for functions that call themselves recursively
+    /// or need to re-register themselves.
+    ///
+    /// The function only holds a weak reference to itself, so it can still be garbage collected.
+    pub fn create_function_recursive<F, R>(&self, func: F) -> Function
+    where
+        F: Fn(&Function, Invocation) -> Result<R> + 'static,
+        R: ToValue,
+    {
+        let this_function: Rc<RefCell<Option<WeakValue>>> = Rc::new(RefCell::new(None));
+        let this_function_clone = this_function.clone();
+        let function = self.create_function(move |invocation| {
+            // The function is alive while it is being called, so the upgrade can't fail:
+            let this_function = this_function_clone.borrow().as_ref()
+                .and_then(|weak| weak.upgrade())
+                .and_then(|value| value.as_function().cloned())
+                .expect("recursive function called without its own handle");
+            func(&this_function, invocation)
+        });
+        *this_function.borrow_mut() = Some(Value::Function(function.clone()).downgrade());
+        function
+    }
+
+    /// Wraps a Rust function or closure like `create_function`, additionally returning a handle
+    /// that revokes the function. Once revoked, calls to the function no longer reach the Rust
+    /// closure and instead throw a JavaScript `TypeError` (converted from `Error::Revoked`). This
+    /// lets hosts cut off untrusted code's access to a callback after handing it out.
+    pub fn create_revocable_function<F, R>(&self, func: F) -> (Function, RevokeHandle)
+    where
+        F: Fn(Invocation) -> Result<R> + 'static,
+        R: ToValue,
+    {
+        let handle = RevokeHandle(Rc::new(Cell::new(false)));
+        let revoked = handle.0.clone();
+        let function = self.create_function(move |invocation| {
+            if revoked.get() {
+                return Err(Error::Revoked);
+            }
+            func(invocation)
+        });
+        (function, handle)
+    }
+
+    /// Notifies V8 that the system is running low on memory, prompting it to perform a full
+    /// garbage collection and release as much memory as possible. This is always available, and is
+    /// useful for cleaning up at idle points in long-lived `MiniV8`s.
+    pub fn low_memory_notification(&self) {
+        self.scope(|scope| scope.low_memory_notification());
+    }
+
+    /// Triggers a garbage collection of the given kind.
+    ///
+    /// This is only available when V8's `--expose-gc` flag has been set (e.g. with
+    /// `v8::V8::set_flags_from_string("--expose-gc")` before the first `MiniV8` is created), and
+    /// returns `Error::GcUnavailable` otherwise. Prefer `MiniV8::low_memory_notification`, which
+    /// needs no flags.
+    pub fn request_gc(&self, kind: GcKind) -> Result<()> {
+        if !self.interface.use_slot(|exposed: &GcExposed| exposed.0) {
+            return Err(Error::GcUnavailable);
+        }
+
+        let kind = match kind {
+            GcKind::Full => v8::GarbageCollectionType::Full,
+            GcKind::Minor => v8::GarbageCollectionType::Minor,
+        };
+        self.scope(|scope| scope.request_garbage_collection_for_testing(kind));
+        Ok(())
+    }
+
+    /// Starts recording a sampled CPU profile of all JavaScript executed in this `MiniV8` until
+    /// `MiniV8::stop_cpu_profile` is called. Starting a profile while another is being recorded
+    /// discards the other profile.
+    ///
+    /// Profiles are recorded through V8's inspector protocol (the `v8` crate doesn't expose V8's
+    /// `CpuProfiler`), sampling every 100µs. Returns an `Error::ExternalError` if the inspector
+    /// fails to start profiling.
+    pub fn start_cpu_profile(&self, name: &str) -> Result<()> {
+        self.scope(|scope| {
+            if let Some(profiler) = scope.remove_slot::<Profiler>() {
+                // The replaced profile is discarded, so errors stopping it are irrelevant:
+                let _ = profiler.stop(scope);
+            }
+            let profiler = Profiler::start(scope, name)?;
+            scope.set_slot(profiler);
+            Ok(())
         })
     }
 
+    /// Stops recording the CPU profile started with `MiniV8::start_cpu_profile`, returning the
+    /// recorded profile. Returns `Error::NoCpuProfile` if no profile is being recorded, and another
+    /// error if the inspector fails to stop profiling or reports a malformed profile.
+    pub fn stop_cpu_profile(&self) -> Result<CpuProfile> {
+        let stopped = self.scope(|scope| {
+            scope.remove_slot::<Profiler>().map(|profiler| profiler.stop(scope))
+        });
+        let (name, response) = stopped.ok_or(Error::NoCpuProfile)??;
+        CpuProfile::from_response(self, name, &response)
+    }
+
     // Opens a new handle scope in the global context. Nesting calls to this or `MiniV8::try_catch`
     // will cause a panic (unless a callback is entered, see `MiniV8::create_function`).
     pub(crate) fn scope<F, T>(&self, func: F) -> T
@@ -278,7 +1466,11 @@ impl MiniV8 {
         if scope.has_terminated() {
             Err(Error::Timeout)
         } else if let Some(exception) = scope.exception() {
-            Err(Error::Value(Value::from_v8_value(self, scope, exception)))
+            let details = exception_details(scope);
+            *scope.get_slot::<LastException>().unwrap().0.borrow_mut() = details;
+            let stack_overflow = is_stack_overflow(scope, exception);
+            let value = Value::from_v8_value(self, scope, exception);
+            Err(if stack_overflow { Error::StackOverflow(value) } else { Error::Value(value) })
         } else {
             Ok(())
         }
@@ -288,7 +1480,20 @@ impl MiniV8 {
 #[derive(Clone)]
 struct Interface(Rc<RefCell<Vec<Rc<RefCell<InterfaceEntry>>>>>);
 
+// A reference to an `Interface` that doesn't keep it alive, for use by state owned by the isolate.
+struct WeakInterface(Weak<RefCell<Vec<Rc<RefCell<InterfaceEntry>>>>>);
+
+impl WeakInterface {
+    fn upgrade(&self) -> Option<Interface> {
+        self.0.upgrade().map(Interface)
+    }
+}
+
 impl Interface {
+    fn downgrade(&self) -> WeakInterface {
+        WeakInterface(Rc::downgrade(&self.0))
+    }
+
     fn len(&self) -> usize {
         self.0.borrow().len()
     }
@@ -393,6 +1598,123 @@ struct Global {
     context: v8::Global<v8::Context>,
 }
 
+// Delegate for `v8::ValueDeserializer` that uses V8's default behavior (e.g. rejecting host
+// objects).
+struct Deserializer;
+
+impl v8::ValueDeserializerImpl for Deserializer {}
+
+// The number of Rust callbacks currently being called, and the limit set with
+// `MiniV8::set_max_callback_depth`.
+struct CallbackDepth {
+    depth: Cell<usize>,
+    max: Cell<Option<usize>>,
+}
+
+impl CallbackDepth {
+    // Records entering a callback, returning `false` if doing so would exceed the limit.
+    fn enter(&self) -> bool {
+        let depth = self.depth.get();
+        if self.max.get().is_some_and(|max| depth >= max) {
+            return false;
+        }
+        self.depth.set(depth + 1);
+        true
+    }
+
+    fn exit(&self) {
+        self.depth.set(self.depth.get() - 1);
+    }
+}
+
+// The amount of stack V8 allows JavaScript to use by default (V8's `--stack-size` flag).
+const DEFAULT_STACK_SIZE: usize = 984 * 1024;
+
+// The message of the exception V8 throws when the call stack overflows, as reported by
+// `v8::Exception::create_message`. See `is_stack_overflow`.
+const STACK_OVERFLOW_MESSAGE: &str = "Uncaught RangeError: Maximum call stack size exceeded";
+
+// How often the heap usage of a sandboxed evaluation is checked.
+const HEAP_POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+// The heap limit of a sandboxed evaluation, kept in an isolate slot while the evaluation runs.
+// Heap usage can only be read from the isolate's own thread, so a polling thread requests
+// interrupts that check it. The interrupts may be serviced after the evaluation finishes (or
+// never), so they look the guard up in the slot rather than holding on to it.
+struct HeapGuard {
+    isolate_handle: v8::IsolateHandle,
+    max_heap_size: usize,
+    exceeded: Cell<bool>,
+    // The isolate's heap limit before `near_heap_limit_callback` raised it, or zero if it didn't.
+    initial_heap_limit: Cell<usize>,
+}
+
+impl HeapGuard {
+    fn exceed(&self) {
+        self.exceeded.set(true);
+        self.isolate_handle.terminate_execution();
+    }
+}
+
+extern "C" fn heap_check_interrupt(isolate: &mut v8::Isolate, _data: *mut c_void) {
+    let guard = match isolate.get_slot::<Rc<HeapGuard>>() {
+        Some(guard) => guard.clone(),
+        None => return,
+    };
+
+    let mut stats = v8::HeapStatistics::default();
+    isolate.get_heap_statistics(&mut stats);
+    if stats.used_heap_size() > guard.max_heap_size {
+        guard.exceed();
+    }
+}
+
+// Called by V8 when the isolate's own heap limit is about to be reached, which would otherwise
+// abort the process. Execution is terminated and V8 is given enough headroom to unwind.
+extern "C" fn near_heap_limit_callback(
+    data: *mut c_void,
+    current_heap_limit: usize,
+    _initial_heap_limit: usize,
+) -> usize {
+    let guard = unsafe { &*(data as *const HeapGuard) };
+    guard.exceed();
+    if guard.initial_heap_limit.get() == 0 {
+        guard.initial_heap_limit.set(current_heap_limit);
+    }
+    current_heap_limit * 2
+}
+
+// Built-in JavaScript functions captured from a pristine context, before any user script has had
+// the chance to replace them.
+pub(crate) struct Builtins {
+    pub(crate) to_fixed: v8::Global<v8::Function>,
+    pub(crate) to_precision: v8::Global<v8::Function>,
+    pub(crate) number_to_string: v8::Global<v8::Function>,
+    pub(crate) json_stringify: v8::Global<v8::Function>,
+    pub(crate) is_frozen: v8::Global<v8::Function>,
+    pub(crate) is_sealed: v8::Global<v8::Function>,
+    pub(crate) array_slice: v8::Global<v8::Function>,
+    pub(crate) array_splice: v8::Global<v8::Function>,
+    pub(crate) array_index_of: v8::Global<v8::Function>,
+    pub(crate) array_includes: v8::Global<v8::Function>,
+    pub(crate) array_set_length: v8::Global<v8::Function>,
+    pub(crate) weak_map: v8::Global<v8::Function>,
+    pub(crate) weak_map_get: v8::Global<v8::Function>,
+    pub(crate) weak_map_set: v8::Global<v8::Function>,
+    pub(crate) weak_map_has: v8::Global<v8::Function>,
+    pub(crate) weak_map_delete: v8::Global<v8::Function>,
+    pub(crate) weak_set: v8::Global<v8::Function>,
+    pub(crate) weak_set_add: v8::Global<v8::Function>,
+    pub(crate) weak_set_has: v8::Global<v8::Function>,
+    pub(crate) weak_set_delete: v8::Global<v8::Function>,
+    pub(crate) eval: v8::Global<v8::Function>,
+    pub(crate) object_assign: v8::Global<v8::Function>,
+    pub(crate) define_property: v8::Global<v8::Function>,
+    pub(crate) regexp: v8::Global<v8::Function>,
+    pub(crate) regexp_source: v8::Global<v8::Function>,
+    pub(crate) loose_equals: v8::Global<v8::Function>,
+}
+
 static INIT: Once = Once::new();
 
 fn initialize_v8() {
@@ -403,6 +1725,25 @@ fn initialize_v8() {
     });
 }
 
+// Returns the approximate address of the top of the current thread's stack.
+#[inline(never)]
+fn stack_position() -> usize {
+    let marker = 0u8;
+    &marker as *const u8 as usize
+}
+
+// Sets the address beyond which the isolate's stack may not grow. The `v8` crate doesn't bind
+// `v8::Isolate::SetStackLimit`, so it's linked directly from the V8 library the crate builds.
+fn set_stack_limit(isolate: &mut v8::Isolate, stack_limit: usize) {
+    extern "C" {
+        #[cfg_attr(not(target_env = "msvc"), link_name = "_ZN2v87Isolate13SetStackLimitEm")]
+        #[cfg_attr(target_env = "msvc", link_name = "?SetStackLimit@Isolate@v8@@QEAAX_K@Z")]
+        fn v8_isolate_set_stack_limit(isolate: *mut v8::Isolate, stack_limit: usize);
+    }
+
+    unsafe { v8_isolate_set_stack_limit(isolate, stack_limit) }
+}
+
 fn initialize_slots(isolate: &mut v8::Isolate) {
     let scope = &mut v8::HandleScope::new(isolate);
     let context = v8::Context::new(scope);
@@ -410,13 +1751,294 @@ fn initialize_slots(isolate: &mut v8::Isolate) {
     let global_context = v8::Global::new(scope, context);
     scope.set_slot(Global { context: global_context });
     scope.set_slot(AnyMap(Rc::new(RefCell::new(BTreeMap::new()))));
+    scope.set_slot(ScriptRegistry(RefCell::new(BTreeMap::new())));
+    scope.set_slot(ModuleNames(RefCell::new(BTreeMap::new())));
+    scope.set_slot(ModuleCache(RefCell::new(BTreeMap::new())));
+    scope.set_slot(ModuleResolutions(RefCell::new(BTreeMap::new())));
+    scope.set_slot(CallbackDepth { depth: Cell::new(0), max: Cell::new(None) });
+    scope.set_slot(LastException(RefCell::new(None)));
+    let gc_exposed = is_gc_exposed(scope, context);
+    scope.set_slot(GcExposed(gc_exposed));
+    let to_fixed = builtin_function(scope, "Number.prototype.toFixed");
+    let to_precision = builtin_function(scope, "Number.prototype.toPrecision");
+    let number_to_string = builtin_function(scope, "Number.prototype.toString");
+    let json_stringify = builtin_function(scope, "JSON.stringify");
+    let is_frozen = builtin_function(scope, "Object.isFrozen");
+    let is_sealed = builtin_function(scope, "Object.isSealed");
+    let array_slice = builtin_function(scope, "Array.prototype.slice");
+    let array_splice = builtin_function(scope, "Array.prototype.splice");
+    let array_index_of = builtin_function(scope, "Array.prototype.indexOf");
+    let array_includes = builtin_function(scope, "Array.prototype.includes");
+    // Assigning in strict mode makes V8 throw its own `TypeError` if the length can't be set:
+    let array_set_length =
+        builtin_function(scope, "(array, len) => { 'use strict'; array.length = len; }");
+    let weak_map = builtin_function(scope, "WeakMap");
+    let weak_map_get = builtin_function(scope, "WeakMap.prototype.get");
+    let weak_map_set = builtin_function(scope, "WeakMap.prototype.set");
+    let weak_map_has = builtin_function(scope, "WeakMap.prototype.has");
+    let weak_map_delete = builtin_function(scope, "WeakMap.prototype.delete");
+    let weak_set = builtin_function(scope, "WeakSet");
+    let weak_set_add = builtin_function(scope, "WeakSet.prototype.add");
+    let weak_set_has = builtin_function(scope, "WeakSet.prototype.has");
+    let weak_set_delete = builtin_function(scope, "WeakSet.prototype.delete");
+    let eval = builtin_function(scope, "eval");
+    let object_assign = builtin_function(scope, "Object.assign");
+    let define_property = builtin_function(scope, "Object.defineProperty");
+    let regexp = builtin_function(scope, "RegExp");
+    let regexp_source =
+        builtin_function(scope, "Object.getOwnPropertyDescriptor(RegExp.prototype, 'source').get");
+    // The `v8` crate doesn't bind `Value::Equals`:
+    let loose_equals = builtin_function(scope, "(a, b) => a == b");
+    scope.set_slot(Builtins {
+        to_fixed,
+        to_precision,
+        number_to_string,
+        json_stringify,
+        is_frozen,
+        is_sealed,
+        array_slice,
+        array_splice,
+        array_index_of,
+        array_includes,
+        array_set_length,
+        weak_map,
+        weak_map_get,
+        weak_map_set,
+        weak_map_has,
+        weak_map_delete,
+        weak_set,
+        weak_set_add,
+        weak_set_has,
+        weak_set_delete,
+        eval,
+        object_assign,
+        define_property,
+        regexp,
+        regexp_source,
+        loose_equals,
+    });
+}
+
+// Returns `true` if V8's `--expose-gc` flag is set. V8 can't report its flags, but with this one
+// set it gives every new context a global `gc` function, so the given context must be pristine
+// (i.e. no script has had the chance to define its own `gc`). Flags can't change once V8 is
+// initialized.
+fn is_gc_exposed(scope: &mut v8::HandleScope, context: v8::Local<v8::Context>) -> bool {
+    let key = create_string(scope, "gc");
+    let global = context.global(scope);
+    let gc = global.get(scope, key.into());
+    gc.is_some_and(|gc| gc.is_function())
+}
+
+fn builtin_function(scope: &mut v8::HandleScope, path: &str) -> v8::Global<v8::Function> {
+    let source = create_string(scope, path);
+    let value = v8::Script::compile(scope, source, None).unwrap().run(scope).unwrap();
+    let function = v8::Local::<v8::Function>::try_from(value).unwrap();
+    v8::Global::new(scope, function)
+}
+
+fn compile_script<'s>(
+    scope: &mut v8::HandleScope<'s>,
+    script: &Script,
+) -> Option<v8::Local<'s, v8::Script>> {
+    let source = create_string(scope, &script.source);
+    let origin = script.origin.as_ref().map(|o| create_script_origin(scope, o));
+    v8::Script::compile(scope, source, origin.as_ref())
+}
+
+fn create_script_origin<'s>(
+    scope: &mut v8::HandleScope<'s>,
+    origin: &ScriptOrigin,
+) -> v8::ScriptOrigin<'s> {
+    let name = create_string(scope, &origin.name).into();
+    let source_map_url = create_string(scope, "").into();
+    v8::ScriptOrigin::new(
+        scope,
+        name,
+        origin.line_offset,
+        origin.column_offset,
+        false,
+        0,
+        source_map_url,
+        true,
+        false,
+        false,
+    )
+}
+
+// Creates the origin of a script whose first line is preceded by a `prefix_len`-character prefix,
+// so that positions within the script (e.g. in stack traces) don't include the prefix. Scripts
+// without an origin are left without a resource name, as when no origin is passed to V8.
+fn prefixed_script_origin<'s>(
+    scope: &mut v8::HandleScope<'s>,
+    origin: Option<&ScriptOrigin>,
+    prefix_len: usize,
+) -> v8::ScriptOrigin<'s> {
+    let column_offset = origin.map_or(0, |o| o.column_offset) - prefix_len as i32;
+    let (name, line_offset, is_opaque) = match origin {
+        Some(origin) => (create_string(scope, &origin.name).into(), origin.line_offset, true),
+        None => (v8::undefined(scope).into(), 0, false),
+    };
+    let source_map_url = create_string(scope, "").into();
+    v8::ScriptOrigin::new(
+        scope,
+        name,
+        line_offset,
+        column_offset,
+        false,
+        0,
+        source_map_url,
+        is_opaque,
+        false,
+        false,
+    )
+}
+
+// Returns a random number, e.g. for a value that JavaScript code shouldn't be able to guess.
+fn random_u64() -> u64 {
+    use std::collections::hash_map::RandomState;
+    use std::hash::{BuildHasher, Hasher};
+
+    RandomState::new().build_hasher().finish()
+}
+
+// The `v8` crate doesn't report whether V8 rejected cache data, so code caches are prefixed with
+// V8's cache version tag (which covers its version and flags) and a hash of the script's source.
+// Cache data with a mismatched header is never passed to V8. This only catches stale data: a
+// rejection by V8 itself (e.g. of corrupt data) still goes unnoticed.
+const CODE_CACHE_HEADER_LEN: usize = 12;
+
+fn code_cache_header(source_hash: u64) -> [u8; CODE_CACHE_HEADER_LEN] {
+    let mut header = [0; CODE_CACHE_HEADER_LEN];
+    let tag = v8::script_compiler::cached_data_version_tag();
+    header[..4].copy_from_slice(&tag.to_le_bytes());
+    header[4..].copy_from_slice(&source_hash.to_le_bytes());
+    header
+}
+
+// A 64-bit FNV-1a hash, which unlike `std`'s hashers is stable across builds.
+fn source_hash(source: &str) -> u64 {
+    source.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+// Adds the compiled script's source to the script registry (see `Script::register`). The `v8`
+// crate doesn't bind `UnboundScript::GetId`, but V8 represents a compiled script as the function
+// holding its top-level code, so the id is read with `Function::ScriptId` instead.
+fn register_script(scope: &mut v8::HandleScope, script: v8::Local<v8::Script>, source: &str) {
+    let function: v8::Local<v8::Function> = unsafe { std::mem::transmute(script) };
+    // `Function::ScriptId` returns -1 (`kNoScriptId`) if it can't find the script:
+    if let Ok(id) = usize::try_from(function.script_id()) {
+        let registry = scope.get_slot::<ScriptRegistry>().unwrap();
+        registry.0.borrow_mut().insert(id, source.to_owned());
+    }
+}
+
+fn resolve_module<'a>(
+    context: v8::Local<'a, v8::Context>,
+    specifier: v8::Local<'a, v8::String>,
+    _import_assertions: v8::Local<'a, v8::FixedArray>,
+    referrer: v8::Local<'a, v8::Module>,
+) -> Option<v8::Local<'a, v8::Module>> {
+    let scope = &mut unsafe { v8::CallbackScope::new(context) };
+    let specifier = specifier.to_rust_string_lossy(scope);
+    let referrer = module_name(scope, referrer);
+    let resolutions = scope.get_slot::<ModuleResolutions>().unwrap();
+    let module = resolutions.0.borrow().get(&(referrer, specifier.clone())).cloned();
+    if let Some(module) = module {
+        return Some(v8::Local::new(scope, module));
+    }
+
+    // All imports are resolved before instantiation, so this should be unreachable:
+    let message = format!("cannot import {:?}: module was not resolved", specifier);
+    let message = create_string(scope, &message);
+    let exception = v8::Exception::error(scope, message);
+    scope.throw_exception(exception);
+    None
+}
+
+// Returns the name the module was compiled with by `MiniV8::eval_module_with_resolver`, or an
+// empty string if the name has been forgotten (e.g. after `MiniV8::clear_module_cache`).
+fn module_name(scope: &mut v8::HandleScope, module: v8::Local<v8::Module>) -> StdString {
+    let hash = module.get_identity_hash().get();
+    let names = scope.get_slot::<ModuleNames>().unwrap().0.borrow();
+    names.get(&hash)
+        .and_then(|bucket| bucket.iter().find(|(handle, _)| module == *handle))
+        .map(|(_, name)| name.clone())
+        .unwrap_or_default()
+}
+
+extern "C" fn initialize_import_meta(
+    context: v8::Local<v8::Context>,
+    module: v8::Local<v8::Module>,
+    meta: v8::Local<v8::Object>,
+) {
+    let scope = &mut unsafe { v8::CallbackScope::new(context) };
+    let (interface, hook) = match scope.get_slot::<ImportMeta>() {
+        Some(import_meta) => (import_meta.interface.upgrade(), import_meta.hook.clone()),
+        None => return,
+    };
+    let mv8 = match interface {
+        Some(interface) => MiniV8 { interface },
+        None => return,
+    };
+    let name = module_name(scope, module);
+
+    let scope = &mut v8::HandleScope::new(scope);
+    let scope = &mut v8::ContextScope::new(scope, context);
+    let scope: &mut v8::HandleScope = scope;
+    let meta = Object { mv8: mv8.clone(), handle: v8::Global::new(scope, meta) };
+    let ptr = scope as *mut v8::HandleScope;
+    // As with function callbacks, the `v8::HandleScope` only lives on the interface stack during
+    // the current block:
+    let ptr: *mut v8::HandleScope<'static> = unsafe { std::mem::transmute(ptr) };
+    mv8.interface.push(ptr);
+    hook(&name, &meta);
+    mv8.interface.pop();
+}
+
+// Reads the details of the exception caught by a `v8::TryCatch`.
+fn exception_details(scope: &mut v8::TryCatch<v8::HandleScope>) -> Option<ExceptionDetails> {
+    let message = scope.message()?;
+    let text = message.get(scope).to_rust_string_lossy(scope);
+    let resource_name = message.get_script_resource_name(scope)
+        .filter(|name| name.is_string())
+        .map(|name| name.to_rust_string_lossy(scope));
+    let source_line = message.get_source_line(scope).map(|line| line.to_rust_string_lossy(scope));
+    let stack_trace = scope.stack_trace()
+        .filter(|stack| stack.is_string())
+        .map(|stack| stack.to_rust_string_lossy(scope));
+    Some(ExceptionDetails {
+        message: text,
+        resource_name,
+        line_number: message.get_line_number(scope),
+        start_column: message.get_start_column(),
+        end_column: message.get_end_column(),
+        source_line,
+        stack_trace,
+    })
+}
+
+// Returns `true` if the exception is the `RangeError` V8 throws when the call stack overflows. The
+// exception's message is read without running any JavaScript (e.g. a `message` getter).
+//
+// V8 doesn't mark stack overflow errors in any other way, and the isolate's stack limit can't be
+// read to compare against, so matching V8's message is the only available check. It misclassifies
+// user-thrown errors with the same message, and must be updated if V8 ever rewords the message.
+fn is_stack_overflow(scope: &mut v8::HandleScope, exception: v8::Local<v8::Value>) -> bool {
+    if !exception.is_native_error() {
+        return false;
+    }
+    let message = v8::Exception::create_message(scope, exception).get(scope);
+    message.to_rust_string_lossy(scope) == STACK_OVERFLOW_MESSAGE
 }
 
 fn create_string<'s>(scope: &mut v8::HandleScope<'s>, value: &str) -> v8::Local<'s, v8::String> {
     v8::String::new(scope, value).expect("string exceeds maximum length")
 }
 
-fn add_finalizer<T: 'static>(
+pub(crate) fn add_finalizer<T: 'static>(
     isolate: &mut v8::Isolate,
     handle: impl v8::Handle<Data = T>,
     finalizer: impl FnOnce() + 'static,
@@ -433,15 +2055,123 @@ fn add_finalizer<T: 'static>(
     rc.replace(Some(weak));
 }
 
-type Callback = Box<dyn Fn(&MiniV8, Value, Values) -> Result<Value>>;
+// Converts the payload of a panic caught in a Rust callback into an error to raise in JavaScript.
+fn panic_error(payload: Box<dyn Any + Send>) -> Error {
+    let message = match payload.downcast_ref::<&str>() {
+        Some(message) => Some(message.to_string()),
+        None => payload.downcast_ref::<StdString>().cloned(),
+    };
+    let message = match message {
+        Some(message) => format!("Rust function panicked: {}", message),
+        None => "Rust function panicked".to_string(),
+    };
+    Error::ExternalError(message.into())
+}
 
-struct CallbackInfo {
-    mv8: MiniV8,
-    callback: Callback,
+type Callback = Rc<dyn Fn(&MiniV8, Value, Values, bool, Value) -> Result<Value>>;
+
+// The Rust functions created with `MiniV8::create_function`. Each V8 function holds the index of
+// its callback as its data, rather than a pointer to a separately allocated callback, and the
+// index is freed for reuse when the V8 function is garbage collected. The interface is weak, since
+// the isolate owns this.
+#[derive(Clone)]
+struct Callbacks(Rc<RefCell<CallbackTable>>);
+
+struct CallbackTable {
+    interface: WeakInterface,
+    entries: Vec<Option<Callback>>,
+    free: Vec<u32>,
+}
+
+impl Callbacks {
+    fn new(interface: WeakInterface) -> Callbacks {
+        Callbacks(Rc::new(RefCell::new(CallbackTable {
+            interface,
+            entries: Vec::new(),
+            free: Vec::new(),
+        })))
+    }
+
+    fn insert(&self, callback: Callback) -> u32 {
+        let mut table = self.0.borrow_mut();
+        match table.free.pop() {
+            Some(index) => {
+                table.entries[index as usize] = Some(callback);
+                index
+            },
+            None => {
+                table.entries.push(Some(callback));
+                (table.entries.len() - 1) as u32
+            },
+        }
+    }
+
+    fn get(&self, index: u32) -> Option<(MiniV8, Callback)> {
+        let table = self.0.borrow();
+        let callback = table.entries.get(index as usize)?.clone()?;
+        let interface = table.interface.upgrade()?;
+        Some((MiniV8 { interface }, callback))
+    }
+
+    fn remove(&self, index: u32) {
+        let callback = {
+            let mut table = self.0.borrow_mut();
+            table.free.push(index);
+            table.entries[index as usize].take()
+        };
+        // The callback is dropped after releasing the table, since dropping its captured values
+        // may run arbitrary code:
+        drop(callback);
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        let table = self.0.borrow();
+        table.entries.len() - table.free.len()
+    }
 }
 
 struct AnyMap(Rc<RefCell<BTreeMap<StdString, Box<dyn Any>>>>);
 
+// Successfully evaluated modules, keyed by name. See `MiniV8::eval_module`.
+struct ModuleCache(RefCell<BTreeMap<StdString, v8::Global<v8::Module>>>);
+
+// The modules imported by each specifier of each module (keyed by the importing module's name and
+// the specifier) of the module graph being instantiated by `MiniV8::eval_module_with_resolver`.
+struct ModuleResolutions(RefCell<BTreeMap<(StdString, StdString), v8::Global<v8::Module>>>);
+
+// The modules loaded by `MiniV8::eval_module_with_resolver` and their imports.
+#[derive(Default)]
+struct ModuleGraph {
+    modules: BTreeMap<StdString, v8::Global<v8::Module>>,
+    resolutions: BTreeMap<(StdString, StdString), v8::Global<v8::Module>>,
+}
+
+// The names of the modules compiled by `MiniV8::eval_module_with_resolver`, for identifying them
+// in callbacks. Modules are keyed by their identity hash, which several modules may share. Names
+// are kept while the modules are cached or being evaluated.
+struct ModuleNames(RefCell<BTreeMap<i32, Vec<NamedModule>>>);
+
+type NamedModule = (v8::Global<v8::Module>, StdString);
+
+// The hook set with `MiniV8::set_import_meta`. The interface is weak, since the isolate owns this.
+struct ImportMeta {
+    interface: WeakInterface,
+    hook: Rc<ImportMetaHook>,
+}
+
+type ImportMetaHook = dyn Fn(&str, &Object);
+
+// The details of the most recent exception returned as an error, see
+// `MiniV8::last_exception_details`.
+struct LastException(RefCell<Option<ExceptionDetails>>);
+
+// Whether V8's `--expose-gc` flag is set, which `MiniV8::request_gc` requires.
+struct GcExposed(bool);
+
+// The sources of scripts evaluated with `Script::register` set, keyed by script id.
+struct ScriptRegistry(RefCell<BTreeMap<usize, StdString>>);
+
 // A JavaScript script.
 #[derive(Clone, Debug, Default)]
 pub struct Script {
@@ -457,6 +2187,197 @@ pub struct Script {
     pub timeout: Option<Duration>,
     /// The script's origin.
     pub origin: Option<ScriptOrigin>,
+    /// Whether to keep the script's source in the `MiniV8`'s script registry once it's compiled, so
+    /// that it can be retrieved by script id with `MiniV8::script_source`. This is useful for
+    /// source-level tooling such as debuggers and profilers.
+    pub register: bool,
+}
+
+/// An ES module loaded by the resolver passed to `MiniV8::eval_module_with_resolver`.
+#[derive(Clone, Debug, Default)]
+pub struct ModuleSource {
+    /// The module's resolved name, which identifies it (e.g. a canonical path or URL).
+    pub name: StdString,
+    /// The source of the module. This is ignored if a module with the same name is cached.
+    pub source: StdString,
+}
+
+/// A JavaScript script that has been compiled with `MiniV8::compile`, and can be run repeatedly
+/// without being recompiled.
+#[derive(Clone)]
+pub struct CompiledScript {
+    mv8: MiniV8,
+    handle: v8::Global<v8::UnboundScript>,
+    timeout: Option<Duration>,
+    source_hash: u64,
+}
+
+impl CompiledScript {
+    /// Runs the script in the `MiniV8`'s context and returns its result. As with `MiniV8::eval`,
+    /// the script's timeout cannot be applied within a nested evaluation, and returns
+    /// `Error::InvalidTimeout` there.
+    pub fn run<R: FromValue>(&self) -> Result<R> {
+        let mv8 = &self.mv8;
+        mv8.with_timeout(self.timeout, || mv8.try_catch(|scope| {
+            let unbound = v8::Local::new(scope, self.handle.clone());
+            let script = unbound.bind_to_current_context(scope);
+            let result = script.run(scope);
+            mv8.exception(scope)?;
+            Ok(Value::from_v8_value(mv8, scope, result.unwrap()))
+        }))?.into(mv8)
+    }
+
+    /// Serializes V8's compilation data for the script, which can be persisted and passed to
+    /// `MiniV8::compile_with_cache` to speed up compiling the same script later, even in another
+    /// process. The data is V8's own cache data behind a small header that lets
+    /// `MiniV8::compile_with_cache` detect stale data. Returns `None` if V8 could not create the
+    /// cache data.
+    pub fn create_code_cache(&self) -> Option<Vec<u8>> {
+        self.mv8.scope(|scope| {
+            let unbound = v8::Local::new(scope, self.handle.clone());
+            let cache = unbound.create_code_cache()?;
+            let mut data = code_cache_header(self.source_hash).to_vec();
+            data.extend_from_slice(&cache);
+            Some(data)
+        })
+    }
+
+    /// Returns an estimate of the size of the script's compiled code in bytes, for budgeting
+    /// script complexity (e.g. rejecting overly complex scripts before running them). V8 exposes
+    /// no direct measure, so this is the size of V8's code cache for the script, without the
+    /// header that `CompiledScript::create_code_cache` adds. V8 compiles most functions lazily, so
+    /// functions that haven't been called yet contribute little. Returns `None` if V8 could not
+    /// create the cache data.
+    pub fn bytecode_size(&self) -> Option<usize> {
+        self.create_code_cache().map(|cache| cache.len() - CODE_CACHE_HEADER_LEN)
+    }
+}
+
+impl fmt::Debug for CompiledScript {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<compiled script>")
+    }
+}
+
+/// Details about a JavaScript exception. See `MiniV8::last_exception_details`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExceptionDetails {
+    /// V8's message for the exception, e.g. `"Uncaught ReferenceError: x is not defined"`.
+    pub message: StdString,
+    /// The name of the script in which the exception was thrown (see `ScriptOrigin::name`), if
+    /// known.
+    pub resource_name: Option<StdString>,
+    /// The one-based line number at which the exception was thrown, including the script
+    /// origin's line offset, if known.
+    pub line_number: Option<usize>,
+    /// The zero-based column within the line at which the expression that threw starts.
+    pub start_column: usize,
+    /// The zero-based column within the line at which the expression that threw ends.
+    pub end_column: usize,
+    /// The source code of the line on which the exception was thrown, if known.
+    pub source_line: Option<StdString>,
+    /// The exception's stack trace (its `stack` property), if it has one.
+    pub stack_trace: Option<StdString>,
+}
+
+/// A handle for terminating JavaScript execution in a `MiniV8` from any thread. See
+/// `MiniV8::thread_safe_handle`.
+#[derive(Clone)]
+pub struct ThreadSafeHandle(v8::IsolateHandle);
+
+impl ThreadSafeHandle {
+    /// Forcefully terminates the currently running JavaScript execution, as happens when a
+    /// `Script::timeout` elapses. The interrupted evaluation returns `Error::Timeout`, and the
+    /// `MiniV8` remains usable afterward. If no JavaScript is running, the next execution is
+    /// terminated instead, unless `ThreadSafeHandle::cancel_terminate_execution` is called first.
+    ///
+    /// Like timeouts, this can only interrupt JavaScript code: Rust code called from JavaScript
+    /// runs until it returns to JavaScript. Returns `false` if the `MiniV8` has been dropped.
+    pub fn terminate_execution(&self) -> bool {
+        self.0.terminate_execution()
+    }
+
+    /// Cancels a termination requested with `ThreadSafeHandle::terminate_execution` that has not
+    /// yet taken effect. Returns `false` if the `MiniV8` has been dropped.
+    pub fn cancel_terminate_execution(&self) -> bool {
+        self.0.cancel_terminate_execution()
+    }
+
+    /// Returns `true` if JavaScript execution is currently being terminated.
+    pub fn is_execution_terminating(&self) -> bool {
+        self.0.is_execution_terminating()
+    }
+}
+
+impl fmt::Debug for ThreadSafeHandle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<thread-safe handle>")
+    }
+}
+
+/// The kind of garbage collection to trigger with `MiniV8::request_gc`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GcKind {
+    /// A full garbage collection of the whole heap.
+    Full,
+    /// A minor garbage collection of only the young generation of the heap.
+    Minor,
+}
+
+/// The level of a `console` message, corresponding to the `console` method that logged it. See
+/// `MiniV8::set_console`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ConsoleLevel {
+    /// `console.log`.
+    Log,
+    /// `console.info`.
+    Info,
+    /// `console.warn`.
+    Warn,
+    /// `console.error`.
+    Error,
+}
+
+/// Where `MiniV8::eval_extract` reads the requested keys from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExtractSource {
+    /// The global object, for scripts that assign their results to global variables.
+    Global,
+    /// The script's result, for scripts that evaluate to an object.
+    Result,
+}
+
+/// The completion value of a script, as returned by `MiniV8::eval_completion`.
+#[derive(Clone, Debug)]
+pub enum Completion {
+    /// The script completed with a value (which may be `undefined`).
+    Value(Value),
+    /// The script completed without a value, e.g. because it only declared variables.
+    Empty,
+}
+
+/// Limits on the resources a sandboxed evaluation may use. See `MiniV8::eval_sandboxed`.
+#[derive(Clone, Debug, Default)]
+pub struct ResourceLimits {
+    /// The maximum runtime duration of the evaluation. See `Script::timeout` for caveats.
+    pub timeout: Option<Duration>,
+    /// The maximum number of bytes the JavaScript heap may occupy during the evaluation. This
+    /// covers the whole heap of the `MiniV8`, including values created before the evaluation.
+    ///
+    /// Heap usage is checked periodically, so an evaluation may briefly overshoot this limit before
+    /// it is terminated.
+    pub max_heap_size: Option<usize>,
+    /// The maximum number of bytes of native stack that JavaScript code may use during the
+    /// evaluation, measured from the point `MiniV8::eval_sandboxed` is called.
+    ///
+    /// V8 can't report the stack limit in effect beforehand, so afterward JavaScript may use V8's
+    /// default amount of stack (about 1 MB), measured from the same point. `MiniV8`s that are
+    /// never given a stack limit keep V8's own limit.
+    pub max_stack_size: Option<usize>,
+    /// Whether code may be generated from strings during the evaluation, i.e. through `eval` or
+    /// the `Function` constructor. Defaults to `false`. The previous setting is restored
+    /// afterward.
+    pub allow_code_generation: bool,
 }
 
 /// The origin, within a file, of a JavaScript script.
@@ -482,6 +2403,75 @@ impl<'a> From<&'a str> for Script {
     }
 }
 
+/// Tracks the position of each entry of a read-eval-print loop session, so that line numbers in
+/// stack traces and syntax errors refer to the session as a whole rather than to each entry.
+#[derive(Clone, Debug)]
+pub struct ReplContext {
+    name: StdString,
+    line_offset: i32,
+}
+
+impl ReplContext {
+    /// Creates a new session whose entries are evaluated with the given origin name, starting at
+    /// the first line.
+    pub fn new<S: Into<StdString>>(name: S) -> ReplContext {
+        ReplContext { name: name.into(), line_offset: 0 }
+    }
+
+    /// Returns the line at which the next entry will start (zero-based).
+    pub fn line_offset(&self) -> i32 {
+        self.line_offset
+    }
+
+    /// Creates a `Script` for the given entry, with an origin starting at the session's current
+    /// line, and advances the session past the entry's lines.
+    pub fn script<S: Into<StdString>>(&mut self, source: S) -> Script {
+        let source = source.into();
+        let origin = ScriptOrigin {
+            name: self.name.clone(),
+            line_offset: self.line_offset,
+            column_offset: 0,
+        };
+        self.line_offset += source.split('\n').count() as i32;
+        Script { source, origin: Some(origin), ..Default::default() }
+    }
+
+    /// Evaluates the given entry with `MiniV8::eval`, advancing the session past the entry's lines
+    /// whether or not evaluation succeeds.
+    pub fn eval<S, R>(&mut self, mv8: &MiniV8, source: S) -> Result<R>
+    where
+        S: Into<StdString>,
+        R: FromValue,
+    {
+        mv8.eval(self.script(source))
+    }
+}
+
+fn execute_with_poll<T>(
+    interval: Duration,
+    execute_fn: impl FnOnce() -> T,
+    poll_fn: impl Fn() + Send + 'static,
+) -> T {
+    let wait = Arc::new((Mutex::new(true), Condvar::new()));
+    let poll_wait = wait.clone();
+    thread::spawn(move || {
+        let (mutex, condvar) = &*poll_wait;
+        let mut is_executing = mutex.lock().unwrap();
+        while *is_executing {
+            is_executing = condvar.wait_timeout(is_executing, interval).unwrap().0;
+            if *is_executing {
+                poll_fn();
+            }
+        }
+    });
+
+    let result = execute_fn();
+    let (mutex, condvar) = &*wait;
+    *mutex.lock().unwrap() = false;
+    condvar.notify_one();
+    result
+}
+
 fn execute_with_timeout<T>(
     timeout: Duration,
     execute_fn: impl FnOnce() -> T,
@@ -1,6 +1,12 @@
 use crate::*;
+use crate::mini_v8::add_finalizer;
+use crate::value::debug_nested;
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::fmt;
 use std::marker::PhantomData;
+use std::rc::Rc;
 
 #[derive(Clone)]
 pub struct Object {
@@ -9,6 +15,27 @@ pub struct Object {
 }
 
 impl Object {
+    /// Returns `true` if this and `other` refer to the same JavaScript object, as `===` would.
+    /// Separate objects are never identical, even if their contents are; see `deep_equals` for
+    /// comparing contents.
+    pub fn ptr_eq(&self, other: &Object) -> bool {
+        self.mv8.scope(|scope| {
+            let value = v8::Local::new(scope, self.handle.clone());
+            let other = v8::Local::new(scope, other.handle.clone());
+            value.strict_equals(other.into())
+        })
+    }
+
+    /// Returns `true` if this object is structurally equal to `other`, i.e. both have the same own
+    /// enumerable keys with deeply equal values. See `Value::deep_equals` for the exact semantics.
+    /// This is also how `PartialEq` compares objects, so `==` may run JavaScript (e.g. getters).
+    ///
+    /// Returns an error if reading a property throws, e.g. from a getter.
+    pub fn deep_equals(&self, other: &Object) -> Result<bool> {
+        let value = Value::Object(self.clone());
+        value.deep_equals(&Value::Object(other.clone()), &self.mv8)
+    }
+
     /// Get an object property value using the given key. Returns `Value::Undefined` if no property
     /// with the key exists.
     ///
@@ -41,6 +68,30 @@ impl Object {
         })
     }
 
+    /// Returns the value of the property at the given key, or if the property does not exist or is
+    /// `undefined`, sets the property to the value returned by `default` and returns that value.
+    /// `default` is only called if the property needs to be set.
+    ///
+    /// Returns an error if `ToValue::to_value` fails for the key or the default value, if
+    /// `FromValue::from_value` fails for the existing value, or if the key value could not be cast
+    /// to a property key string.
+    pub fn get_or_insert_with<K, V, F>(&self, key: K, default: F) -> Result<V>
+    where
+        K: ToValue,
+        V: FromValue + ToValue,
+        F: FnOnce() -> V,
+    {
+        let key = key.to_value(&self.mv8)?;
+        let existing: Value = self.get(key.clone())?;
+        if !existing.is_undefined() {
+            return existing.into(&self.mv8);
+        }
+
+        let value = default().to_value(&self.mv8)?;
+        self.set(key, value.clone())?;
+        value.into(&self.mv8)
+    }
+
     /// Removes the property associated with the given key from the object. This function does
     /// nothing if the property does not exist.
     ///
@@ -56,6 +107,234 @@ impl Object {
         })
     }
 
+    /// Removes all own enumerable properties from the object, which is useful for reusing objects
+    /// (e.g. from a pool).
+    ///
+    /// Properties that are not configurable cannot be deleted and will remain on the object. In
+    /// strict mode JavaScript would throw here; this function silently skips them instead.
+    pub fn clear(&self) -> Result<()> {
+        self.mv8.try_catch(|scope| {
+            let object = v8::Local::new(scope, self.handle.clone());
+            let keys = object.get_own_property_names(scope, Default::default());
+            self.mv8.exception(scope)?;
+            let keys = keys.unwrap();
+            for index in 0..keys.length() {
+                let key = keys.get_index(scope, index);
+                self.mv8.exception(scope)?;
+                object.delete(scope, key.unwrap());
+                self.mv8.exception(scope)?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Copies the own enumerable properties of `source` onto the object, as JavaScript's
+    /// `Object.assign(object, source)` would. The source's getters are called to read its values,
+    /// and the object's setters are called to assign them.
+    ///
+    /// Returns an `Error::Value` holding the exception if a getter or setter throws, or if the
+    /// object does not allow a property to be set (e.g. because it is frozen). Properties copied
+    /// before the error remain copied.
+    pub fn assign(&self, source: &Object) -> Result<()> {
+        let assign = self.mv8.builtin(|builtins| &builtins.object_assign);
+        assign.call::<_, Value>((self.clone(), source.clone())).map(|_| ())
+    }
+
+    /// Defines an accessor property on the object whose value is computed by calling `getter`, with
+    /// `this` set to the object. This is shorthand for `Object::define_accessor` without a setter,
+    /// so any existing setter for the property is removed.
+    pub fn define_getter<K: ToValue>(&self, key: K, getter: Function) -> Result<()> {
+        self.define_accessor(key, Some(getter), None)
+    }
+
+    /// Defines an accessor property on the object whose assignments call `setter` with the new
+    /// value, with `this` set to the object. This is shorthand for `Object::define_accessor`
+    /// without a getter, so any existing getter for the property is removed.
+    pub fn define_setter<K: ToValue>(&self, key: K, setter: Function) -> Result<()> {
+        self.define_accessor(key, None, Some(setter))
+    }
+
+    /// Defines an enumerable and configurable accessor property on the object, replacing any
+    /// existing property with the key. Reading the property calls `getter` (or evaluates to
+    /// `undefined` without one), and assigning to it calls `setter` (or is ignored without one).
+    /// Either function can be created with `MiniV8::create_function` to back the property with
+    /// Rust.
+    ///
+    /// Returns an error if `ToValue::to_value` fails for the key, if the key value could not be
+    /// cast to a property key string, or if the object does not allow the property to be defined
+    /// (e.g. because it is frozen).
+    pub fn define_accessor<K: ToValue>(
+        &self,
+        key: K,
+        getter: Option<Function>,
+        setter: Option<Function>,
+    ) -> Result<()> {
+        self.define_accessor_property(key, AccessorDescriptor {
+            getter,
+            setter,
+            enumerable: true,
+            configurable: true,
+        })
+    }
+
+    /// Defines an accessor property on the object with the given descriptor, replacing any
+    /// existing property with the key. This is like `Object::define_accessor`, but allows making
+    /// the property non-enumerable (omitting it from `Object::keys` and `Object::properties`) or
+    /// non-configurable. Assigning to an accessor property without a setter throws a `TypeError`
+    /// in strict mode code, which makes getter-only accessors suitable for read-only computed
+    /// properties.
+    ///
+    /// Returns an error if `ToValue::to_value` fails for the key, or if the key value could not be
+    /// cast to a property key string. Returns an `Error::Value` holding a JavaScript `TypeError` if
+    /// the object does not allow the property to be defined.
+    pub fn define_accessor_property<K>(&self, key: K, descriptor: AccessorDescriptor) -> Result<()>
+    where
+        K: ToValue,
+    {
+        let key = key.to_value(&self.mv8)?;
+        let accessor = |f: Option<Function>| f.map_or(Value::Undefined, Value::Function);
+        self.define(key, vec![
+            ("get", accessor(descriptor.getter)),
+            ("set", accessor(descriptor.setter)),
+            ("enumerable", Value::Boolean(descriptor.enumerable)),
+            ("configurable", Value::Boolean(descriptor.configurable)),
+        ])
+    }
+
+    /// Defines a data property on the object with the given descriptor, as JavaScript's
+    /// `Object.defineProperty` would. Properties that are not enumerable are omitted from
+    /// `Object::keys` and `Object::properties`.
+    ///
+    /// Returns an error if `ToValue::to_value` fails for the key, or if the key value could not be
+    /// cast to a property key string. Returns an `Error::Value` holding a JavaScript `TypeError` if
+    /// the object does not allow the property to be defined (e.g. because an existing property
+    /// with the key is not configurable, or the object is frozen).
+    pub fn define_property<K>(&self, key: K, descriptor: PropertyDescriptor) -> Result<()>
+    where
+        K: ToValue,
+    {
+        let key = key.to_value(&self.mv8)?;
+        self.define(key, vec![
+            ("value", descriptor.value),
+            ("writable", Value::Boolean(descriptor.writable)),
+            ("enumerable", Value::Boolean(descriptor.enumerable)),
+            ("configurable", Value::Boolean(descriptor.configurable)),
+        ])
+    }
+
+    // Defines a property with the original `Object.defineProperty`, which throws a `TypeError` if
+    // the property can't be defined, where V8's own `DefineOwnProperty` just reports failure. The
+    // descriptor object has no prototype, so that properties added to `Object.prototype` (e.g. a
+    // `get`) can't change its meaning.
+    fn define(&self, key: Value, fields: Vec<(&str, Value)>) -> Result<()> {
+        let descriptor = self.mv8.create_object_with_prototype(Value::Null)?;
+        for (name, value) in fields {
+            descriptor.set(name, value)?;
+        }
+        let define_property = self.mv8.builtin(|builtins| &builtins.define_property);
+        define_property.call::<_, Value>((self.clone(), key, descriptor)).map(|_| ())
+    }
+
+    /// Returns the descriptor of the object's own property with the given key, as JavaScript's
+    /// `Object.getOwnPropertyDescriptor` would, or `None` if the object has no such own property.
+    /// For accessor properties, the descriptor's `value` is `undefined` and `writable` is `false`.
+    ///
+    /// Returns an error if `ToValue::to_value` fails for the key or if the key value could not be
+    /// cast to a property key string.
+    pub fn get_own_property_descriptor<K: ToValue>(
+        &self,
+        key: K,
+    ) -> Result<Option<PropertyDescriptor>> {
+        let key = key.to_value(&self.mv8)?;
+        let descriptor = self.mv8.try_catch(|scope| {
+            let object = v8::Local::new(scope, self.handle.clone());
+            let key = property_name(scope, &key);
+            self.mv8.exception(scope)?;
+            let descriptor = object.get_own_property_descriptor(scope, key.unwrap());
+            self.mv8.exception(scope)?;
+            Ok(Value::from_v8_value(&self.mv8, scope, descriptor.unwrap()))
+        })?;
+
+        let descriptor = match descriptor {
+            Value::Object(descriptor) => descriptor,
+            _ => return Ok(None),
+        };
+
+        Ok(Some(PropertyDescriptor {
+            value: descriptor.get("value")?,
+            writable: descriptor.get("writable")?,
+            enumerable: descriptor.get("enumerable")?,
+            configurable: descriptor.get("configurable")?,
+        }))
+    }
+
+    /// Returns the prototype of the object, or `Value::Null` if the object has no prototype.
+    pub fn get_prototype(&self) -> Result<Value> {
+        self.mv8.try_catch(|scope| {
+            let object = v8::Local::new(scope, self.handle.clone());
+            let prototype = object.get_prototype(scope);
+            self.mv8.exception(scope)?;
+            Ok(Value::from_v8_value(&self.mv8, scope, prototype.unwrap()))
+        })
+    }
+
+    /// Sets the prototype of the object, as JavaScript's `Object.setPrototypeOf` would. Pass
+    /// `Value::Null` to remove the object's prototype.
+    ///
+    /// Returns an `Error::Value` holding a JavaScript `TypeError` if the prototype is neither an
+    /// object nor `null`, if it would create a cycle in the prototype chain, or if the object is
+    /// not extensible.
+    pub fn set_prototype(&self, prototype: Value) -> Result<()> {
+        self.mv8.try_catch(|scope| {
+            let object = v8::Local::new(scope, self.handle.clone());
+            let prototype = prototype.to_v8_value(scope);
+            if !prototype.is_object() && !prototype.is_null() {
+                let message = "Object prototype may only be an Object or null";
+                let message = v8::String::new(scope, message).unwrap();
+                let error = v8::Exception::type_error(scope, message);
+                return Err(Error::Value(Value::from_v8_value(&self.mv8, scope, error)));
+            }
+            object.set_prototype(scope, prototype);
+            self.mv8.exception(scope)
+        })
+    }
+
+    /// Freezes the object, as JavaScript's `Object.freeze` would: no properties can be added,
+    /// removed, or changed afterward.
+    ///
+    /// Later attempts to change the object through `Object::set` or `Object::remove` are silently
+    /// ignored, as they would be in sloppy mode JavaScript. Strict mode scripts that attempt to
+    /// change the object throw a `TypeError`.
+    pub fn freeze(&self) -> Result<()> {
+        self.set_integrity_level(v8::IntegrityLevel::Frozen)
+    }
+
+    /// Seals the object, as JavaScript's `Object.seal` would: no properties can be added or
+    /// removed afterward, but existing writable properties can still be changed. Later attempts to
+    /// add or remove properties behave as described in `Object::freeze`.
+    pub fn seal(&self) -> Result<()> {
+        self.set_integrity_level(v8::IntegrityLevel::Sealed)
+    }
+
+    /// Returns `true` if the object is frozen, as JavaScript's `Object.isFrozen` would.
+    pub fn is_frozen(&self) -> Result<bool> {
+        self.mv8.builtin(|builtins| &builtins.is_frozen).call((self.clone(),))
+    }
+
+    /// Returns `true` if the object is sealed, as JavaScript's `Object.isSealed` would. Frozen
+    /// objects are also sealed.
+    pub fn is_sealed(&self) -> Result<bool> {
+        self.mv8.builtin(|builtins| &builtins.is_sealed).call((self.clone(),))
+    }
+
+    fn set_integrity_level(&self, level: v8::IntegrityLevel) -> Result<()> {
+        self.mv8.try_catch(|scope| {
+            let object = v8::Local::new(scope, self.handle.clone());
+            object.set_integrity_level(scope, level);
+            self.mv8.exception(scope)
+        })
+    }
+
     /// Returns `true` if the given key is a property of the object, `false` otherwise.
     ///
     /// Returns an error if `ToValue::to_value` fails for the key or if the key value could not be
@@ -83,11 +362,51 @@ impl Object {
         func.call_method(self.clone(), args)
     }
 
+    /// Calls the function at the key with the given arguments like `Object::call_prop`, but
+    /// discards the function's return value and returns the object itself, so that calls to
+    /// mutating methods can be chained, e.g.
+    /// `object.call_prop_chained("a", ())?.call_prop_chained("b", ())?`.
+    pub fn call_prop_chained<K, A>(&self, key: K, args: A) -> Result<Object>
+    where
+        K: ToValue,
+        A: ToValues,
+    {
+        self.call_prop::<_, _, Value>(key, args)?;
+        Ok(self.clone())
+    }
+
+    /// Defines a method on the object at the given key, backed by a Rust function or closure. The
+    /// method receives the object it was called on as its `this` argument.
+    ///
+    /// This is the cycle-safe way of attaching Rust methods to an object: a closure passed to
+    /// `MiniV8::create_function` that captures the very object it is stored on holds a strong
+    /// reference to that object, so neither can ever be garbage collected. By reading the object
+    /// from the invocation's `this` instead, the closure never keeps its owner alive.
+    ///
+    /// When called, the method returns a `TypeError` to JavaScript if its `this` is not an object.
+    /// See `Object::set` for how this method might return an error.
+    pub fn define_method_weak<K, F, R>(&self, key: K, func: F) -> Result<()>
+    where
+        K: ToValue,
+        F: Fn(&MiniV8, Object, Values) -> Result<R> + 'static,
+        R: ToValue,
+    {
+        let method = self.mv8.create_function(move |inv| {
+            let this: Object = inv.this.into(&inv.mv8)?;
+            func(&inv.mv8, this, inv.args)
+        });
+        self.set(key, method)
+    }
+
     /// Returns an array containing all of this object's enumerable property keys. If
     /// `include_inherited` is `false`, then only the object's own enumerable properties will be
     /// collected (similar to `Object.getOwnPropertyNames` in Javascript). If `include_inherited` is
     /// `true`, then the object's own properties and the enumerable properties from its prototype
     /// chain will be collected.
+    ///
+    /// The returned array holds every key at once, which can be a lot of memory for huge objects.
+    /// V8 can't enumerate keys incrementally; see `Object::key_at` for reading keys one at a time
+    /// without holding onto them all.
     pub fn keys(&self, include_inherited: bool) -> Result<Array> {
         self.mv8.try_catch(|scope| {
             let object = v8::Local::new(scope, self.handle.clone());
@@ -104,6 +423,173 @@ impl Object {
         })
     }
 
+    /// Returns the own enumerable property key at the given index, in the same order as
+    /// `Object::keys(false)`, or `None` if the index is not less than `Object::len`.
+    ///
+    /// This lets callers page through the keys of a huge object without holding an array of all
+    /// of them. V8 still enumerates every key on each call (the enumeration is discarded
+    /// afterward), so paging through all keys this way takes quadratic time; prefer
+    /// `Object::keys` unless memory is the greater concern.
+    pub fn key_at(&self, index: u32) -> Result<Option<Value>> {
+        self.mv8.try_catch(|scope| {
+            let object = v8::Local::new(scope, self.handle.clone());
+            let keys = object.get_own_property_names(scope, Default::default());
+            self.mv8.exception(scope)?;
+            let keys = keys.unwrap();
+            if index >= keys.length() {
+                return Ok(None);
+            }
+            let key = keys.get_index(scope, index);
+            self.mv8.exception(scope)?;
+            Ok(Some(Value::from_v8_value(&self.mv8, scope, key.unwrap())))
+        })
+    }
+
+    /// Returns the number of the object's own enumerable property keys, i.e. the length of
+    /// `Object::keys(false)`, without creating an array handle for the keys.
+    pub fn len(&self) -> Result<u32> {
+        self.mv8.try_catch(|scope| {
+            let object = v8::Local::new(scope, self.handle.clone());
+            let keys = object.get_own_property_names(scope, Default::default());
+            self.mv8.exception(scope)?;
+            Ok(keys.unwrap().length())
+        })
+    }
+
+    /// Returns `true` if the object has no own enumerable property keys, `false` otherwise.
+    pub fn is_empty(&self) -> Result<bool> {
+        self.len().map(|len| len == 0)
+    }
+
+    /// Returns the number of internal fields of the object, which is zero unless the object was
+    /// created with `MiniV8::create_object_with_internal_fields`.
+    pub fn internal_field_count(&self) -> usize {
+        self.mv8.scope(|scope| v8::Local::new(scope, self.handle.clone()).internal_field_count())
+    }
+
+    /// Stores Rust data in the internal field at the given index, replacing any data previously
+    /// stored there. Internal fields are invisible to JavaScript, and their data is dropped when
+    /// the object is garbage collected (or when it is replaced).
+    ///
+    /// Returns `Error::OutOfBounds` if the index is not less than `Object::internal_field_count`.
+    pub fn set_internal_field<T: Any>(&self, index: usize, data: T) -> Result<()> {
+        let data: Rc<dyn Any> = Rc::new(data);
+        let replaced = self.mv8.scope(|scope| {
+            let object = v8::Local::new(scope, self.handle.clone());
+            let len = object.internal_field_count();
+            if index >= len {
+                return Err(Error::OutOfBounds { index, len });
+            }
+
+            let fields = match internal_fields(scope, object) {
+                Some(fields) => fields,
+                None => {
+                    let fields = Rc::new(InternalFields::new(vec![None; len]));
+                    let ptr = Rc::into_raw(fields.clone());
+                    let ext = v8::External::new(scope, ptr as _);
+                    object.set_internal_field(0, ext.into());
+                    add_finalizer(scope, object, move || drop(unsafe { Rc::from_raw(ptr) }));
+                    fields
+                },
+            };
+            let replaced = fields.borrow_mut()[index].replace(data);
+            Ok(replaced)
+        })?;
+        // The replaced data is dropped outside of the scope, in case its destructor uses V8:
+        drop(replaced);
+        Ok(())
+    }
+
+    /// Returns the Rust data stored in the internal field at the given index with
+    /// `Object::set_internal_field`, or `None` if no data of type `T` is stored there.
+    ///
+    /// Returns `Error::OutOfBounds` if the index is not less than `Object::internal_field_count`.
+    pub fn get_internal_field<T: Any>(&self, index: usize) -> Result<Option<Rc<T>>> {
+        self.mv8.scope(|scope| {
+            let object = v8::Local::new(scope, self.handle.clone());
+            let len = object.internal_field_count();
+            if index >= len {
+                return Err(Error::OutOfBounds { index, len });
+            }
+
+            let data = internal_fields(scope, object).and_then(|f| f.borrow()[index].clone());
+            Ok(data.and_then(|data| data.downcast::<T>().ok()))
+        })
+    }
+
+    /// Returns an estimate of the size of the object graph reachable from this object through own
+    /// enumerable properties, counted as the number of values in the graph (including this
+    /// object). Every object is counted once no matter how often it is referenced, so cyclic
+    /// graphs are supported. Property keys are not counted.
+    ///
+    /// This is useful for rejecting overly large values produced by scripts. Returns an error if
+    /// reading any property throws an exception (e.g. from a getter).
+    pub fn estimated_size(&self) -> Result<usize> {
+        self.mv8.try_catch(|scope| {
+            // Objects are bucketed by identity hash, which is not unique but narrows comparisons:
+            let mut visited: HashMap<i32, Vec<v8::Local<v8::Object>>> = HashMap::new();
+            let mut stack = vec![v8::Local::new(scope, self.handle.clone())];
+            let mut count = 0;
+            while let Some(object) = stack.pop() {
+                let bucket = visited.entry(i32::from(object.get_identity_hash())).or_default();
+                if bucket.contains(&object) {
+                    continue;
+                }
+                bucket.push(object);
+                count += 1;
+
+                let keys = object.get_own_property_names(scope, Default::default());
+                self.mv8.exception(scope)?;
+                let keys = keys.unwrap();
+                for i in 0..keys.length() {
+                    let key = keys.get_index(scope, i).unwrap();
+                    let value = object.get(scope, key);
+                    self.mv8.exception(scope)?;
+                    match v8::Local::<v8::Object>::try_from(value.unwrap()) {
+                        Ok(child) => stack.push(child),
+                        Err(_) => count += 1,
+                    }
+                }
+            }
+            Ok(count)
+        })
+    }
+
+    /// Returns an iterator over the object's keys and values, acting like a `for-in` loop. Unlike
+    /// `Object::properties`, this leaves the object usable afterward (the iterator holds its own
+    /// reference to the object).
+    ///
+    /// For information on the `include_inherited` argument, see `Object::keys`.
+    pub fn iter<K, V>(&self, include_inherited: bool) -> Result<Properties<K, V>>
+    where
+        K: FromValue,
+        V: FromValue,
+    {
+        self.clone().properties(include_inherited)
+    }
+
+    /// Collects the object's keys and values into a `Vec`, like JavaScript's `Object.entries`
+    /// (though with `include_inherited` set to `true`, inherited properties are included too).
+    /// Returns the first error encountered while reading or converting a key or value.
+    ///
+    /// For information on the `include_inherited` argument, see `Object::keys`.
+    pub fn entries<K, V>(&self, include_inherited: bool) -> Result<Vec<(K, V)>>
+    where
+        K: FromValue,
+        V: FromValue,
+    {
+        self.iter(include_inherited)?.collect()
+    }
+
+    /// Collects the object's values into a `Vec`, like JavaScript's `Object.values`. Returns the
+    /// first error encountered while reading or converting a value.
+    ///
+    /// For information on the `include_inherited` argument, see `Object::keys`.
+    pub fn values<V: FromValue>(&self, include_inherited: bool) -> Result<Vec<V>> {
+        let keys = self.keys(include_inherited)?;
+        keys.elements::<Value>().map(|key| self.get(key?)).collect()
+    }
+
     /// Converts the object into an iterator over the object's keys and values, acting like a
     /// `for-in` loop.
     ///
@@ -118,34 +604,103 @@ impl Object {
     }
 }
 
+type InternalFields = RefCell<Vec<Option<Rc<dyn Any>>>>;
+
+// Returns the Rust data stored in an object's internal fields by `Object::set_internal_field`, if
+// any field has been set. The data of all of an object's fields is kept in one table, referenced
+// from its first internal field and released by a single finalizer. Only
+// `Object::set_internal_field` can set internal fields, so a `v8::External` found there points to
+// a table.
+fn internal_fields(
+    scope: &mut v8::HandleScope,
+    object: v8::Local<v8::Object>,
+) -> Option<Rc<InternalFields>> {
+    let value = object.get_internal_field(scope, 0)?;
+    let ext = v8::Local::<v8::External>::try_from(value).ok()?;
+    let ptr = ext.value() as *const InternalFields;
+    // The object holds a strong reference to the table until it is garbage collected, which can't
+    // happen while a handle to the object exists:
+    unsafe {
+        Rc::increment_strong_count(ptr);
+        Some(Rc::from_raw(ptr))
+    }
+}
+
+/// A description of a data property of an object. See `Object::define_property`.
+#[derive(Clone, Debug)]
+pub struct PropertyDescriptor {
+    /// The value of the property.
+    pub value: Value,
+    /// Whether the value of the property can be changed.
+    pub writable: bool,
+    /// Whether the property shows up when enumerating the object's properties.
+    pub enumerable: bool,
+    /// Whether the property can be deleted, and whether its descriptor can be changed.
+    pub configurable: bool,
+}
+
+/// A description of an accessor property of an object. See `Object::define_accessor_property`.
+#[derive(Clone, Debug)]
+pub struct AccessorDescriptor {
+    /// The function called when reading the property, if any.
+    pub getter: Option<Function>,
+    /// The function called when assigning to the property, if any.
+    pub setter: Option<Function>,
+    /// Whether the property shows up when enumerating the object's properties.
+    pub enumerable: bool,
+    /// Whether the property can be deleted, and whether its descriptor can be changed.
+    pub configurable: bool,
+}
+
+/// Compares objects structurally with `Object::deep_equals`, which reads their properties and so
+/// may run JavaScript getters. Errors thrown while comparing are treated as inequality, and are not
+/// reported by `MiniV8::last_exception_details`.
+impl PartialEq for Object {
+    fn eq(&self, other: &Object) -> bool {
+        self.mv8.preserving_last_exception(|| self.deep_equals(other).unwrap_or(false))
+    }
+}
+
 impl fmt::Debug for Object {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let keys = match self.keys(false) {
-            Ok(keys) => keys,
-            Err(_) => return write!(f, "<object with keys exception>"),
-        };
-
-        let len = keys.len();
-        if len == 0 {
-            return write!(f, "{{}}");
+        // Proxies are not inspected, as doing so would run their traps:
+        if self.mv8.scope(|scope| v8::Local::new(scope, self.handle.clone()).is_proxy()) {
+            return write!(f, "<proxy>");
         }
 
-        write!(f, "{{ ")?;
-        for i in 0..len {
-            if let Ok(k) = keys.get::<Value>(i).and_then(|k| k.coerce_string(&self.mv8)) {
-                write!(f, "{:?}: ", k)?;
-                match self.get::<_, Value>(k) {
-                    Ok(v) => write!(f, "{:?}", v)?,
-                    Err(_) => write!(f, "?")?,
-                };
-            } else {
-                write!(f, "?")?;
+        debug_nested(&self.mv8, f, "{...}", |f| {
+            let keys = match self.keys(false) {
+                Ok(keys) => keys,
+                Err(_) => return write!(f, "<object with keys exception>"),
+            };
+
+            // Frozen objects are marked so that logs convey their immutability:
+            if let Ok(true) = self.is_frozen() {
+                write!(f, "[frozen] ")?;
             }
-            if i + 1 < len {
-                write!(f, ", ")?;
+
+            let len = keys.len();
+            if len == 0 {
+                return write!(f, "{{}}");
             }
-        }
-        write!(f, " }}")
+
+            write!(f, "{{ ")?;
+            for i in 0..len {
+                if let Ok(k) = keys.get::<Value>(i).and_then(|k| k.coerce_string(&self.mv8)) {
+                    write!(f, "{:?}: ", k)?;
+                    match self.get::<_, Value>(k) {
+                        Ok(v) => write!(f, "{:?}", v)?,
+                        Err(_) => write!(f, "?")?,
+                    };
+                } else {
+                    write!(f, "?")?;
+                }
+                if i + 1 < len {
+                    write!(f, ", ")?;
+                }
+            }
+            write!(f, " }}")
+        })
     }
 }
 
@@ -192,3 +747,16 @@ where
         Some(Ok((key, value)))
     }
 }
+
+// Converts a key into a property name, coercing it to a string unless it is already a string or a
+// symbol. Returns `None` if coercion throws an exception.
+fn property_name<'s>(
+    scope: &mut v8::HandleScope<'s>,
+    key: &Value,
+) -> Option<v8::Local<'s, v8::Name>> {
+    let key = key.to_v8_value(scope);
+    match v8::Local::<v8::Name>::try_from(key) {
+        Ok(name) => Some(name),
+        Err(_) => key.to_string(scope).map(|name| name.into()),
+    }
+}
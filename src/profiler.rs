@@ -0,0 +1,319 @@
+use crate::*;
+use std::collections::BTreeMap;
+use std::string::String as StdString;
+use std::time::Duration;
+use v8::inspector::{ChannelBase, ChannelImpl, StringBuffer, StringView};
+use v8::inspector::{V8Inspector, V8InspectorClientBase, V8InspectorClientImpl};
+use v8::inspector::{V8InspectorClientTrustLevel, V8InspectorSession};
+
+/// A sampled CPU profile, recorded between calls to `MiniV8::start_cpu_profile` and
+/// `MiniV8::stop_cpu_profile`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CpuProfile {
+    /// The name given to `MiniV8::start_cpu_profile`.
+    pub name: StdString,
+    /// The time elapsed between starting and stopping the profile.
+    pub duration: Duration,
+    /// The total number of samples taken.
+    pub sample_count: usize,
+    /// The functions that were sampled, ordered by descending self time. This includes V8's
+    /// pseudo-functions, such as `(program)` and `(garbage collector)`.
+    pub functions: Vec<ProfiledFunction>,
+    /// The full profile as JSON, in the `.cpuprofile` format understood by Chrome's DevTools.
+    pub json: StdString,
+}
+
+/// A function's share of a `CpuProfile`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProfiledFunction {
+    /// The function's name, which is empty for anonymous functions and top-level script code.
+    pub name: StdString,
+    /// The name of the script that defined the function, which is empty for functions without a
+    /// script.
+    pub url: StdString,
+    /// The zero-based line number of the function's definition, if known.
+    pub line_number: Option<u32>,
+    /// The zero-based column number of the function's definition, if known.
+    pub column_number: Option<u32>,
+    /// The number of samples taken while this function was at the top of the stack.
+    pub sample_count: usize,
+    /// The approximate time spent in the function itself.
+    pub self_time: Duration,
+    /// The approximate time spent in the function and everything it called.
+    pub total_time: Duration,
+}
+
+// The inspector session backing a running CPU profile, kept in an isolate slot. The `v8` crate
+// doesn't bind V8's `CpuProfiler`, so profiles are recorded through the inspector protocol's
+// `Profiler` domain instead, as Chrome's DevTools do. Fields are dropped in declaration order, so
+// the session is disconnected before the inspector, client and channel it refers to are destroyed.
+pub(crate) struct Profiler {
+    session: v8::UniqueRef<V8InspectorSession>,
+    _inspector: v8::UniqueRef<V8Inspector>,
+    channel: Box<ProfilerChannel>,
+    _client: Box<ProfilerClient>,
+    name: StdString,
+    next_id: i32,
+}
+
+impl Profiler {
+    pub(crate) fn start(scope: &mut v8::HandleScope, name: &str) -> Result<Profiler> {
+        let mut client = Box::new(ProfilerClient {
+            base: V8InspectorClientBase::new::<ProfilerClient>(),
+        });
+        let mut channel = Box::new(ProfilerChannel {
+            base: ChannelBase::new::<ProfilerChannel>(),
+            responses: Vec::new(),
+        });
+        let mut inspector = V8Inspector::create(scope, &mut *client);
+        let context = scope.get_current_context();
+        inspector.context_created(
+            context,
+            CONTEXT_GROUP_ID,
+            StringView::empty(),
+            StringView::empty(),
+        );
+        let session = inspector.connect(
+            CONTEXT_GROUP_ID,
+            &mut *channel,
+            StringView::empty(),
+            V8InspectorClientTrustLevel::FullyTrusted,
+        );
+        let mut profiler = Profiler {
+            session,
+            _inspector: inspector,
+            channel,
+            _client: client,
+            name: name.to_owned(),
+            next_id: 0,
+        };
+        profiler.dispatch(scope, "Profiler.enable", "{}")?;
+        profiler.dispatch(scope, "Profiler.setSamplingInterval", &format!(
+            r#"{{"interval":{}}}"#,
+            SAMPLING_INTERVAL.as_micros(),
+        ))?;
+        profiler.dispatch(scope, "Profiler.start", "{}")?;
+        Ok(profiler)
+    }
+
+    // Stops profiling and returns the profile's name and its JSON representation.
+    pub(crate) fn stop(mut self, scope: &mut v8::HandleScope) -> Result<(StdString, StdString)> {
+        let response = self.dispatch(scope, "Profiler.stop", "{}")?;
+        self.dispatch(scope, "Profiler.disable", "{}")?;
+        Ok((self.name, response))
+    }
+
+    // Sends a protocol message to the session, returning the response. The inspector handles
+    // profiler messages synchronously, so the response is available once this returns. Returns an
+    // error if there is no response or if the response is a protocol error.
+    fn dispatch(
+        &mut self,
+        scope: &mut v8::HandleScope,
+        method: &str,
+        params: &str,
+    ) -> Result<StdString> {
+        self.next_id += 1;
+        let message = format!(
+            r#"{{"id":{},"method":"{}","params":{}}}"#,
+            self.next_id,
+            method,
+            params,
+        );
+        self.session.dispatch_protocol_message(StringView::from(message.as_bytes()));
+        let response = self.channel.responses.pop().ok_or_else(|| {
+            Error::ExternalError(format!("inspector did not respond to {}", method).into())
+        })?;
+        match protocol_error(scope, &response) {
+            Some(error) => {
+                Err(Error::ExternalError(format!("{} failed: {}", method, error).into()))
+            },
+            None => Ok(response),
+        }
+    }
+}
+
+impl CpuProfile {
+    // Summarizes the result of the inspector's `Profiler.stop` method.
+    pub(crate) fn from_response(mv8: &MiniV8, name: StdString, response: &str) -> Result<Self> {
+        let response: Object = mv8.parse_json(response)?.into(mv8)?;
+        let profile: Object = response.get::<_, Object>("result")?.get("profile")?;
+
+        let mut nodes = BTreeMap::new();
+        let mut root = None;
+        for node in profile.get::<_, Array>("nodes")?.elements::<Object>() {
+            let node = node?;
+            let id: u32 = node.get("id")?;
+            let frame: Object = node.get("callFrame")?;
+            let position = |key: &str| -> Result<Option<u32>> {
+                let position: i64 = frame.get(key)?;
+                Ok(if position < 0 { None } else { Some(position as u32) })
+            };
+            let key = FunctionKey {
+                name: frame.get("functionName")?,
+                url: frame.get("url")?,
+                line_number: position("lineNumber")?,
+                column_number: position("columnNumber")?,
+            };
+            let children: Option<Vec<u32>> = node.get("children")?;
+            root.get_or_insert(id);
+            nodes.insert(id, Node {
+                key,
+                children: children.unwrap_or_default(),
+                sample_count: 0,
+                self_time: Duration::ZERO,
+            });
+        }
+
+        let samples: Vec<u32> = profile.get::<_, Option<_>>("samples")?.unwrap_or_default();
+        let deltas: Vec<i64> = profile.get::<_, Option<_>>("timeDeltas")?.unwrap_or_default();
+        for (id, delta) in samples.iter().zip(deltas.iter()) {
+            if let Some(node) = nodes.get_mut(id) {
+                node.sample_count += 1;
+                node.self_time += Duration::from_micros((*delta).max(0) as u64);
+            }
+        }
+
+        let mut functions = BTreeMap::new();
+        if let Some(root) = root {
+            summarize(&nodes, root, &mut Vec::new(), &mut functions);
+        }
+        let mut functions: Vec<ProfiledFunction> = functions
+            .into_iter()
+            .filter(|(key, _)| key.name != "(root)")
+            .map(|(_, function)| function)
+            .collect();
+        functions.sort_by(|a, b| {
+            b.self_time.cmp(&a.self_time).then(b.sample_count.cmp(&a.sample_count))
+        });
+
+        let start_time: f64 = profile.get("startTime")?;
+        let end_time: f64 = profile.get("endTime")?;
+        let json = Value::Object(profile).to_json(mv8)?.to_string();
+        Ok(CpuProfile {
+            name,
+            duration: Duration::from_micros((end_time - start_time).max(0.0) as u64),
+            sample_count: samples.len(),
+            functions,
+            json,
+        })
+    }
+}
+
+// Returns the message of the error in an inspector protocol response, if the response is an error.
+fn protocol_error(scope: &mut v8::HandleScope, response: &str) -> Option<StdString> {
+    let response = v8::String::new(scope, response)?;
+    let response = v8::json::parse(scope, response)?.to_object(scope)?;
+    let key = v8::String::new(scope, "error")?;
+    let error = response.get(scope, key.into())?;
+    if !error.is_object() {
+        return None;
+    }
+    let key = v8::String::new(scope, "message")?;
+    let message = error.to_object(scope)?.get(scope, key.into())?;
+    Some(message.to_rust_string_lossy(scope))
+}
+
+const CONTEXT_GROUP_ID: i32 = 1;
+// V8's default sampling interval is 1ms, which yields few samples for short evaluations, so a
+// finer interval is used. DevTools offers the same 100µs interval as its high-resolution option.
+const SAMPLING_INTERVAL: Duration = Duration::from_micros(100);
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct FunctionKey {
+    name: StdString,
+    url: StdString,
+    line_number: Option<u32>,
+    column_number: Option<u32>,
+}
+
+struct Node {
+    key: FunctionKey,
+    children: Vec<u32>,
+    sample_count: usize,
+    self_time: Duration,
+}
+
+// Adds the samples of the node with the given ID and its descendants to the per-function totals,
+// returning the node's total time. A function's total time only counts its outermost calls, so
+// that recursion isn't counted more than once.
+fn summarize(
+    nodes: &BTreeMap<u32, Node>,
+    id: u32,
+    active: &mut Vec<FunctionKey>,
+    functions: &mut BTreeMap<FunctionKey, ProfiledFunction>,
+) -> Duration {
+    let node = match nodes.get(&id) {
+        Some(node) => node,
+        None => return Duration::ZERO,
+    };
+
+    let outermost = !active.contains(&node.key);
+    active.push(node.key.clone());
+    let mut total_time = node.self_time;
+    for &child in &node.children {
+        total_time += summarize(nodes, child, active, functions);
+    }
+    active.pop();
+
+    let function = functions.entry(node.key.clone()).or_insert_with(|| ProfiledFunction {
+        name: node.key.name.clone(),
+        url: node.key.url.clone(),
+        line_number: node.key.line_number,
+        column_number: node.key.column_number,
+        sample_count: 0,
+        self_time: Duration::ZERO,
+        total_time: Duration::ZERO,
+    });
+    function.sample_count += node.sample_count;
+    function.self_time += node.self_time;
+    if outermost {
+        function.total_time += total_time;
+    }
+    total_time
+}
+
+struct ProfilerClient {
+    base: V8InspectorClientBase,
+}
+
+impl V8InspectorClientImpl for ProfilerClient {
+    fn base(&self) -> &V8InspectorClientBase {
+        &self.base
+    }
+
+    fn base_mut(&mut self) -> &mut V8InspectorClientBase {
+        &mut self.base
+    }
+
+    unsafe fn base_ptr(this: *const Self) -> *const V8InspectorClientBase {
+        std::ptr::addr_of!((*this).base)
+    }
+}
+
+struct ProfilerChannel {
+    base: ChannelBase,
+    responses: Vec<StdString>,
+}
+
+impl ChannelImpl for ProfilerChannel {
+    fn base(&self) -> &ChannelBase {
+        &self.base
+    }
+
+    fn base_mut(&mut self) -> &mut ChannelBase {
+        &mut self.base
+    }
+
+    unsafe fn base_ptr(this: *const Self) -> *const ChannelBase {
+        std::ptr::addr_of!((*this).base)
+    }
+
+    fn send_response(&mut self, _call_id: i32, message: v8::UniquePtr<StringBuffer>) {
+        let message = message.unwrap();
+        self.responses.push(message.string().to_string());
+    }
+
+    fn send_notification(&mut self, _message: v8::UniquePtr<StringBuffer>) {}
+
+    fn flush_protocol_notifications(&mut self) {}
+}
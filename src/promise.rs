@@ -0,0 +1,72 @@
+use crate::*;
+use std::fmt;
+
+/// Reference to a JavaScript promise.
+#[derive(Clone)]
+pub struct Promise {
+    pub(crate) mv8: MiniV8,
+    pub(crate) handle: v8::Global<v8::Promise>,
+}
+
+impl Promise {
+    /// Consumes the promise and downgrades it to a JavaScript object.
+    pub fn into_object(self) -> Object {
+        self.mv8.clone().scope(|scope| {
+            let object: v8::Local<v8::Object> = v8::Local::new(scope, self.handle.clone()).into();
+            Object {
+                mv8: self.mv8,
+                handle: v8::Global::new(scope, object),
+            }
+        })
+    }
+
+    /// Returns the current state of the promise.
+    ///
+    /// Reactions to a promise (e.g. callbacks registered with `then`) are run as microtasks. See
+    /// `MiniV8::run_microtasks` for driving them to completion.
+    pub fn state(&self) -> PromiseState {
+        self.mv8.scope(|scope| {
+            match v8::Local::new(scope, self.handle.clone()).state() {
+                v8::PromiseState::Pending => PromiseState::Pending,
+                v8::PromiseState::Fulfilled => PromiseState::Fulfilled,
+                v8::PromiseState::Rejected => PromiseState::Rejected,
+            }
+        })
+    }
+
+    /// Returns the value the promise was fulfilled with or the reason it was rejected with, or
+    /// `None` if the promise is still pending.
+    pub fn result(&self) -> Option<Value> {
+        self.mv8.scope(|scope| {
+            let promise = v8::Local::new(scope, self.handle.clone());
+            match promise.state() {
+                v8::PromiseState::Pending => None,
+                _ => {
+                    let result = promise.result(scope);
+                    Some(Value::from_v8_value(&self.mv8, scope, result))
+                },
+            }
+        })
+    }
+}
+
+impl fmt::Debug for Promise {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.state() {
+            PromiseState::Pending => write!(f, "<promise: pending>"),
+            PromiseState::Fulfilled => write!(f, "<promise: fulfilled>"),
+            PromiseState::Rejected => write!(f, "<promise: rejected>"),
+        }
+    }
+}
+
+/// The state of a JavaScript promise.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PromiseState {
+    /// The promise has neither been fulfilled nor rejected yet.
+    Pending,
+    /// The promise has been fulfilled with a value.
+    Fulfilled,
+    /// The promise has been rejected with a reason.
+    Rejected,
+}
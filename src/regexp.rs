@@ -0,0 +1,109 @@
+use crate::*;
+use std::fmt;
+use std::string::String as StdString;
+
+/// Reference to a JavaScript regular expression.
+#[derive(Clone)]
+pub struct RegExp {
+    pub(crate) mv8: MiniV8,
+    pub(crate) handle: v8::Global<v8::RegExp>,
+}
+
+impl RegExp {
+    /// Consumes the regular expression and downgrades it to a JavaScript object.
+    pub fn into_object(self) -> Object {
+        self.mv8.clone().scope(|scope| {
+            let object: v8::Local<v8::Object> = v8::Local::new(scope, self.handle.clone()).into();
+            Object {
+                mv8: self.mv8,
+                handle: v8::Global::new(scope, object),
+            }
+        })
+    }
+
+    /// Returns the source text of the regular expression's pattern.
+    pub fn source(&self) -> StdString {
+        // The original `RegExp.prototype.source` getter can't throw for a genuine regular
+        // expression:
+        let source = self.mv8.builtin(|builtins| &builtins.regexp_source);
+        source.call_method(self.clone(), ()).unwrap()
+    }
+
+    /// Returns the regular expression's flags.
+    pub fn flags(&self) -> Result<RegExpFlags> {
+        let flags: StdString = self.clone().into_object().get("flags")?;
+        Ok(RegExpFlags::parse(&flags))
+    }
+
+    /// Returns `true` if the regular expression matches the input, `false` otherwise. This calls
+    /// the regular expression's JavaScript `test` method, so for global or sticky regular
+    /// expressions it starts at and updates the `lastIndex` property.
+    pub fn test(&self, input: &str) -> Result<bool> {
+        self.clone().into_object().call_prop("test", (input,))
+    }
+
+    /// Matches the regular expression against the input by calling its JavaScript `exec` method.
+    /// Returns the match array (the full match followed by any captured groups), or `None` if
+    /// there was no match.
+    pub fn exec(&self, input: &str) -> Result<Option<Array>> {
+        self.clone().into_object().call_prop("exec", (input,))
+    }
+}
+
+impl fmt::Debug for RegExp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.mv8.preserving_last_exception(|| {
+            match self.flags() {
+                Ok(flags) => write!(f, "/{}/{}", self.source(), flags.to_flags_string()),
+                Err(_) => write!(f, "/{}/?", self.source()),
+            }
+        })
+    }
+}
+
+/// The flags of a JavaScript regular expression.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RegExpFlags {
+    /// Find all matches rather than stopping after the first (`g`).
+    pub global: bool,
+    /// Ignore case while matching (`i`).
+    pub ignore_case: bool,
+    /// Let `^` and `$` match at line boundaries (`m`).
+    pub multiline: bool,
+    /// Let `.` match newlines (`s`).
+    pub dot_all: bool,
+    /// Treat the pattern as a sequence of Unicode code points (`u`).
+    pub unicode: bool,
+    /// Only match starting at the `lastIndex` property (`y`).
+    pub sticky: bool,
+}
+
+impl RegExpFlags {
+    fn parse(flags: &str) -> RegExpFlags {
+        RegExpFlags {
+            global: flags.contains('g'),
+            ignore_case: flags.contains('i'),
+            multiline: flags.contains('m'),
+            dot_all: flags.contains('s'),
+            unicode: flags.contains('u'),
+            sticky: flags.contains('y'),
+        }
+    }
+
+    pub(crate) fn to_flags_string(self) -> StdString {
+        let mut flags = StdString::new();
+        for &(set, flag) in &[
+            (self.global, 'g'),
+            (self.ignore_case, 'i'),
+            (self.multiline, 'm'),
+            (self.dot_all, 's'),
+            (self.unicode, 'u'),
+            (self.sticky, 'y'),
+        ] {
+            if set {
+                flags.push(flag);
+            }
+        }
+        flags
+    }
+}
@@ -0,0 +1,647 @@
+//! Conversions between JavaScript values and Rust types implementing `serde`'s traits.
+//!
+//! This module is only available with the `serde` feature enabled.
+
+use crate::*;
+use ::serde::de::{self, DeserializeOwned, DeserializeSeed, IntoDeserializer, Unexpected, Visitor};
+use ::serde::ser::{self, Serialize};
+use std::fmt::Display;
+use std::string::String as StdString;
+
+/// Converts a Rust value implementing `serde::Serialize` into a JavaScript value.
+///
+/// Structs and maps become objects, sequences and tuples become arrays, `None` and unit values
+/// become `null`, and enum variants are externally tagged: a unit variant becomes its name as a
+/// string, and any other variant becomes an object with a single property named after the variant.
+/// 128-bit integers, and 64-bit integers beyond JavaScript's safe integer range (`±(2^53 - 1)`),
+/// become `BigInt`s so that no precision is lost. All other numbers become JavaScript numbers.
+pub fn to_value<T: Serialize + ?Sized>(mv8: &MiniV8, value: &T) -> Result<Value> {
+    value.serialize(Serializer { mv8 })
+}
+
+/// Converts a JavaScript value into a Rust value implementing `serde::Deserialize`.
+///
+/// This is the inverse of `to_value`. Objects, arrays, strings, numbers, booleans, `null` and
+/// `undefined` are supported, as are `BigInt`s for integer targets and array buffers and typed
+/// arrays for byte buffers. Missing object properties deserialize to `None` for `Option` fields.
+///
+/// Unlike `FromValue` for the primitive number types, numbers are not silently truncated or
+/// coerced: deserializing a non-integral or out-of-range number into an integer type, or a string
+/// into a number type, returns an error.
+pub fn from_value<T: DeserializeOwned>(value: Value, mv8: &MiniV8) -> Result<T> {
+    T::deserialize(Deserializer { mv8, value })
+}
+
+impl ser::Error for Error {
+    fn custom<T: Display>(msg: T) -> Error {
+        Error::ExternalError(msg.to_string().into())
+    }
+}
+
+impl de::Error for Error {
+    fn custom<T: Display>(msg: T) -> Error {
+        Error::ExternalError(msg.to_string().into())
+    }
+}
+
+struct Serializer<'a> {
+    mv8: &'a MiniV8,
+}
+
+impl<'a> ser::Serializer for Serializer<'a> {
+    type Ok = Value;
+    type Error = Error;
+    type SerializeSeq = SerializeArray<'a>;
+    type SerializeTuple = SerializeArray<'a>;
+    type SerializeTupleStruct = SerializeArray<'a>;
+    type SerializeTupleVariant = SerializeTupleVariant<'a>;
+    type SerializeMap = SerializeObject<'a>;
+    type SerializeStruct = SerializeObject<'a>;
+    type SerializeStructVariant = SerializeStructVariant<'a>;
+
+    fn serialize_bool(self, v: bool) -> Result<Value> {
+        Ok(Value::Boolean(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Value> {
+        v.to_value(self.mv8)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Value> {
+        v.to_value(self.mv8)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Value> {
+        v.to_value(self.mv8)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Value> {
+        if v.unsigned_abs() > MAX_SAFE_INTEGER {
+            (v as i128).to_value(self.mv8)
+        } else {
+            v.to_value(self.mv8)
+        }
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<Value> {
+        v.to_value(self.mv8)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Value> {
+        v.to_value(self.mv8)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Value> {
+        v.to_value(self.mv8)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Value> {
+        v.to_value(self.mv8)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Value> {
+        if v > MAX_SAFE_INTEGER {
+            (v as u128).to_value(self.mv8)
+        } else {
+            v.to_value(self.mv8)
+        }
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<Value> {
+        v.to_value(self.mv8)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Value> {
+        v.to_value(self.mv8)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Value> {
+        v.to_value(self.mv8)
+    }
+
+    fn serialize_char(self, v: char) -> Result<Value> {
+        v.to_string().to_value(self.mv8)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Value> {
+        v.to_value(self.mv8)
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Value> {
+        v.to_vec().to_value(self.mv8)
+    }
+
+    fn serialize_none(self) -> Result<Value> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Value> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Value> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Value> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Value> {
+        variant.to_value(self.mv8)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Value> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Value> {
+        tag_variant(self.mv8, variant, to_value(self.mv8, value)?)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<SerializeArray<'a>> {
+        Ok(SerializeArray { mv8: self.mv8, array: self.mv8.create_array() })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SerializeArray<'a>> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeArray<'a>> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeTupleVariant<'a>> {
+        Ok(SerializeTupleVariant { variant, inner: self.serialize_seq(Some(len))? })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<SerializeObject<'a>> {
+        Ok(SerializeObject { mv8: self.mv8, object: self.mv8.create_object(), key: None })
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<SerializeObject<'a>> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeStructVariant<'a>> {
+        Ok(SerializeStructVariant { variant, inner: self.serialize_map(Some(len))? })
+    }
+}
+
+// Wraps the value of an enum variant in an object with a single property named after the variant.
+fn tag_variant(mv8: &MiniV8, variant: &'static str, value: Value) -> Result<Value> {
+    let object = mv8.create_object();
+    object.set(variant, value)?;
+    Ok(Value::Object(object))
+}
+
+struct SerializeArray<'a> {
+    mv8: &'a MiniV8,
+    array: Array,
+}
+
+impl<'a> ser::SerializeSeq for SerializeArray<'a> {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.array.push(to_value(self.mv8, value)?)
+    }
+
+    fn end(self) -> Result<Value> {
+        Ok(Value::Array(self.array))
+    }
+}
+
+impl<'a> ser::SerializeTuple for SerializeArray<'a> {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Value> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl<'a> ser::SerializeTupleStruct for SerializeArray<'a> {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Value> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+struct SerializeTupleVariant<'a> {
+    variant: &'static str,
+    inner: SerializeArray<'a>,
+}
+
+impl<'a> ser::SerializeTupleVariant for SerializeTupleVariant<'a> {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        ser::SerializeSeq::serialize_element(&mut self.inner, value)
+    }
+
+    fn end(self) -> Result<Value> {
+        let mv8 = self.inner.mv8;
+        tag_variant(mv8, self.variant, ser::SerializeSeq::end(self.inner)?)
+    }
+}
+
+struct SerializeObject<'a> {
+    mv8: &'a MiniV8,
+    object: Object,
+    key: Option<Value>,
+}
+
+impl<'a> ser::SerializeMap for SerializeObject<'a> {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<()> {
+        self.key = Some(to_value(self.mv8, key)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let key = self.key.take().expect("`serialize_value` called before `serialize_key`");
+        self.object.set(key, to_value(self.mv8, value)?)
+    }
+
+    fn end(self) -> Result<Value> {
+        Ok(Value::Object(self.object))
+    }
+}
+
+impl<'a> ser::SerializeStruct for SerializeObject<'a> {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        self.object.set(key, to_value(self.mv8, value)?)
+    }
+
+    fn end(self) -> Result<Value> {
+        Ok(Value::Object(self.object))
+    }
+}
+
+struct SerializeStructVariant<'a> {
+    variant: &'static str,
+    inner: SerializeObject<'a>,
+}
+
+impl<'a> ser::SerializeStructVariant for SerializeStructVariant<'a> {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        ser::SerializeStruct::serialize_field(&mut self.inner, key, value)
+    }
+
+    fn end(self) -> Result<Value> {
+        let mv8 = self.inner.mv8;
+        tag_variant(mv8, self.variant, ser::SerializeStruct::end(self.inner)?)
+    }
+}
+
+// The largest integer that JavaScript numbers represent exactly, along with all smaller ones:
+const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+
+struct Deserializer<'a> {
+    mv8: &'a MiniV8,
+    value: Value,
+}
+
+impl<'a> Deserializer<'a> {
+    fn checked<T>(self, to: &'static str) -> Result<T>
+    where
+        Checked<T>: FromValue,
+    {
+        match self.value {
+            value @ Value::Number(_) => Ok(Checked::<T>::from_value(value, self.mv8)?.0),
+            value => Err(Error::from_js_conversion(value.type_name(), to)),
+        }
+    }
+}
+
+macro_rules! deserialize_checked {
+    ($method:ident, $visit:ident, $prim_ty:ty) => {
+        fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+            visitor.$visit(self.checked::<$prim_ty>(stringify!($prim_ty))?)
+        }
+    }
+}
+
+impl<'a, 'de> de::Deserializer<'de> for Deserializer<'a> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.value {
+            Value::Undefined | Value::Null => visitor.visit_unit(),
+            Value::Boolean(b) => visitor.visit_bool(b),
+            Value::Number(n) => {
+                if n.fract() == 0.0 && n >= i64::MIN as f64 && n < i64::MAX as f64 {
+                    visitor.visit_i64(n as i64)
+                } else if n.fract() == 0.0 && n >= 0.0 && n < u64::MAX as f64 {
+                    visitor.visit_u64(n as u64)
+                } else {
+                    visitor.visit_f64(n)
+                }
+            },
+            Value::BigInt(b) => match b.to_i128() {
+                Some(n) => visitor.visit_i128(n),
+                None => match b.to_u128() {
+                    Some(n) => visitor.visit_u128(n),
+                    None => Err(Error::from_js_conversion("bigint", "i128")),
+                },
+            },
+            Value::Date(d) => visitor.visit_f64(d),
+            Value::String(s) => visitor.visit_string(s.to_string()),
+            Value::Array(a) => {
+                visitor.visit_seq(SeqAccess { mv8: self.mv8, elements: a.elements() })
+            },
+            Value::Object(o) => visitor.visit_map(MapAccess {
+                mv8: self.mv8,
+                properties: o.properties(false)?,
+                value: None,
+            }),
+            value => Err(Error::from_js_conversion(value.type_name(), "deserializable value")),
+        }
+    }
+
+    deserialize_checked!(deserialize_i8, visit_i8, i8);
+    deserialize_checked!(deserialize_i16, visit_i16, i16);
+    deserialize_checked!(deserialize_i32, visit_i32, i32);
+    deserialize_checked!(deserialize_u8, visit_u8, u8);
+    deserialize_checked!(deserialize_u16, visit_u16, u16);
+    deserialize_checked!(deserialize_u32, visit_u32, u32);
+    deserialize_checked!(deserialize_f32, visit_f32, f32);
+    deserialize_checked!(deserialize_f64, visit_f64, f64);
+
+    fn deserialize_i64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.value {
+            Value::BigInt(b) => {
+                let n = b.to_i128().and_then(|n| i64::try_from(n).ok());
+                visitor.visit_i64(n.ok_or_else(|| Error::from_js_conversion("bigint", "i64"))?)
+            },
+            _ => visitor.visit_i64(self.checked::<i64>("i64")?),
+        }
+    }
+
+    fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.value {
+            Value::BigInt(b) => {
+                let n = b.to_u128().and_then(|n| u64::try_from(n).ok());
+                visitor.visit_u64(n.ok_or_else(|| Error::from_js_conversion("bigint", "u64"))?)
+            },
+            _ => visitor.visit_u64(self.checked::<u64>("u64")?),
+        }
+    }
+
+    fn deserialize_i128<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.value {
+            Value::BigInt(b) => visitor.visit_i128(i128::from_value(Value::BigInt(b), self.mv8)?),
+            _ => visitor.visit_i128(self.checked::<i64>("i128")? as i128),
+        }
+    }
+
+    fn deserialize_u128<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.value {
+            Value::BigInt(b) => visitor.visit_u128(u128::from_value(Value::BigInt(b), self.mv8)?),
+            _ => visitor.visit_u128(self.checked::<u64>("u128")? as u128),
+        }
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.value {
+            Value::ArrayBuffer(b) => visitor.visit_byte_buf(b.to_vec()),
+            Value::TypedArray(a) => visitor.visit_byte_buf(a.copy_to_vec::<u8>()?),
+            value => {
+                de::Deserializer::deserialize_any(Deserializer { mv8: self.mv8, value }, visitor)
+            },
+        }
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.value {
+            Value::Undefined | Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        let mv8 = self.mv8;
+        match self.value {
+            Value::String(s) => visitor.visit_enum(EnumAccess {
+                mv8,
+                variant: s.to_string(),
+                value: None,
+            }),
+            Value::Object(o) => {
+                let mut properties = o.properties::<String, Value>(false)?;
+                let (variant, value) = match (properties.next(), properties.next()) {
+                    (Some(property), None) => property?,
+                    _ => {
+                        let expected = &"an object with a single property";
+                        return Err(de::Error::invalid_value(Unexpected::Map, expected));
+                    },
+                };
+                visitor.visit_enum(EnumAccess {
+                    mv8,
+                    variant: variant.to_string(),
+                    value: Some(value),
+                })
+            },
+            value => Err(Error::from_js_conversion(value.type_name(), "enum")),
+        }
+    }
+
+    ::serde::forward_to_deserialize_any! {
+        bool char str string unit unit_struct seq tuple tuple_struct map struct identifier
+        ignored_any
+    }
+}
+
+struct SeqAccess<'a> {
+    mv8: &'a MiniV8,
+    elements: Elements<Value>,
+}
+
+impl<'a, 'de> de::SeqAccess<'de> for SeqAccess<'a> {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        match self.elements.next() {
+            Some(value) => {
+                seed.deserialize(Deserializer { mv8: self.mv8, value: value? }).map(Some)
+            },
+            None => Ok(None),
+        }
+    }
+}
+
+struct MapAccess<'a> {
+    mv8: &'a MiniV8,
+    properties: Properties<String, Value>,
+    value: Option<Value>,
+}
+
+impl<'a, 'de> de::MapAccess<'de> for MapAccess<'a> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        match self.properties.next() {
+            Some(property) => {
+                let (key, value) = property?;
+                self.value = Some(value);
+                let key = Deserializer { mv8: self.mv8, value: Value::String(key) };
+                seed.deserialize(key).map(Some)
+            },
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let value = self.value.take().expect("`next_value_seed` called before `next_key_seed`");
+        seed.deserialize(Deserializer { mv8: self.mv8, value })
+    }
+}
+
+struct EnumAccess<'a> {
+    mv8: &'a MiniV8,
+    variant: StdString,
+    value: Option<Value>,
+}
+
+impl<'a, 'de> de::EnumAccess<'de> for EnumAccess<'a> {
+    type Error = Error;
+    type Variant = VariantAccess<'a>;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, VariantAccess<'a>)>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let variant = seed.deserialize(self.variant.into_deserializer())?;
+        Ok((variant, VariantAccess { mv8: self.mv8, value: self.value }))
+    }
+}
+
+struct VariantAccess<'a> {
+    mv8: &'a MiniV8,
+    value: Option<Value>,
+}
+
+impl<'a> VariantAccess<'a> {
+    fn into_inner(self, expected: &'static str) -> Result<Deserializer<'a>> {
+        match self.value {
+            Some(value) => Ok(Deserializer { mv8: self.mv8, value }),
+            None => Err(de::Error::invalid_type(Unexpected::UnitVariant, &expected)),
+        }
+    }
+}
+
+impl<'a, 'de> de::VariantAccess<'de> for VariantAccess<'a> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        match self.value {
+            None | Some(Value::Undefined) | Some(Value::Null) => Ok(()),
+            Some(_) => Err(de::Error::invalid_type(Unexpected::NewtypeVariant, &"unit variant")),
+        }
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        seed.deserialize(self.into_inner("newtype variant")?)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value> {
+        de::Deserializer::deserialize_seq(self.into_inner("tuple variant")?, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        de::Deserializer::deserialize_map(self.into_inner("struct variant")?, visitor)
+    }
+}
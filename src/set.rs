@@ -0,0 +1,113 @@
+use crate::*;
+use std::fmt;
+
+/// Reference to a JavaScript `Set`.
+#[derive(Clone)]
+pub struct Set {
+    pub(crate) mv8: MiniV8,
+    pub(crate) handle: v8::Global<v8::Set>,
+}
+
+impl Set {
+    /// Consumes the set and downgrades it to a JavaScript object.
+    pub fn into_object(self) -> Object {
+        self.mv8.clone().scope(|scope| {
+            let object: v8::Local<v8::Object> = v8::Local::new(scope, self.handle.clone()).into();
+            Object {
+                mv8: self.mv8,
+                handle: v8::Global::new(scope, object),
+            }
+        })
+    }
+
+    /// Adds the given value to the set. This does nothing if the value is already in the set.
+    ///
+    /// Returns an error if `ToValue::to_value` fails for the value.
+    pub fn add<V: ToValue>(&self, value: V) -> Result<()> {
+        let value = value.to_value(&self.mv8)?;
+        self.mv8.try_catch(|scope| {
+            let set = v8::Local::new(scope, self.handle.clone());
+            let value = value.to_v8_value(scope);
+            set.add(scope, value);
+            self.mv8.exception(scope)
+        })
+    }
+
+    /// Removes the given value from the set, like JavaScript's `Set.prototype.delete`. Returns
+    /// `true` if the value was removed, `false` if it was not in the set.
+    ///
+    /// Returns an error if `ToValue::to_value` fails for the value.
+    pub fn remove<V: ToValue>(&self, value: V) -> Result<bool> {
+        let value = value.to_value(&self.mv8)?;
+        self.mv8.try_catch(|scope| {
+            let set = v8::Local::new(scope, self.handle.clone());
+            let value = value.to_v8_value(scope);
+            let removed = set.delete(scope, value);
+            self.mv8.exception(scope)?;
+            Ok(removed.unwrap())
+        })
+    }
+
+    /// Returns `true` if the given value is in the set, `false` otherwise.
+    ///
+    /// Returns an error if `ToValue::to_value` fails for the value.
+    pub fn has<V: ToValue>(&self, value: V) -> Result<bool> {
+        let value = value.to_value(&self.mv8)?;
+        self.mv8.try_catch(|scope| {
+            let set = v8::Local::new(scope, self.handle.clone());
+            let value = value.to_v8_value(scope);
+            let has = set.has(scope, value);
+            self.mv8.exception(scope)?;
+            Ok(has.unwrap())
+        })
+    }
+
+    /// Returns the number of values in the set (like JavaScript's `Set.prototype.size`).
+    pub fn len(&self) -> usize {
+        self.mv8.scope(|scope| v8::Local::new(scope, self.handle.clone()).size())
+    }
+
+    /// Returns `true` if the set has no values.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Converts the set into an iterator over its values, in insertion order.
+    ///
+    /// The values are read when this method is called; later changes to the set are not reflected
+    /// by the iterator.
+    pub fn values<V: FromValue>(self) -> Elements<V> {
+        let values = self.mv8.scope(|scope| {
+            let values = v8::Local::new(scope, self.handle.clone()).as_array(scope);
+            Array {
+                mv8: self.mv8.clone(),
+                handle: v8::Global::new(scope, values),
+            }
+        });
+        values.elements()
+    }
+}
+
+impl fmt::Debug for Set {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.mv8.preserving_last_exception(|| {
+            let values = match self.clone().values::<Value>().collect::<Result<Vec<_>>>() {
+                Ok(values) => values,
+                Err(_) => return write!(f, "<set with values exception>"),
+            };
+
+            if values.is_empty() {
+                return write!(f, "Set {{}}");
+            }
+
+            write!(f, "Set {{ ")?;
+            for (i, v) in values.iter().enumerate() {
+                write!(f, "{:?}", v)?;
+                if i + 1 < values.len() {
+                    write!(f, ", ")?;
+                }
+            }
+            write!(f, " }}")
+        })
+    }
+}
@@ -1,20 +1,33 @@
 use crate::*;
+use std::cell::RefCell;
 use std::fmt;
+use std::rc::Rc;
 use std::string::String as StdString;
 
 #[derive(Clone)]
 pub struct String {
     pub(crate) mv8: MiniV8,
     pub(crate) handle: v8::Global<v8::String>,
+    pub(crate) cache: RefCell<Option<Rc<str>>>,
 }
 
 impl String {
     /// Returns a Rust string converted from the V8 string.
+    #[allow(clippy::inherent_to_string)]
     pub fn to_string(&self) -> StdString {
         self.mv8.scope(|scope| {
             v8::Local::new(scope, self.handle.clone()).to_rust_string_lossy(scope)
         })
     }
+
+    /// Returns a Rust string converted from the V8 string, decoding it only on the first call.
+    ///
+    /// JavaScript strings are immutable, so the decoded string is memoized and shared by later
+    /// calls on this handle. This is useful for strings (e.g. property keys) that are read many
+    /// times. Clones of this handle made after the first call share the cached string as well.
+    pub fn as_cached_str(&self) -> Rc<str> {
+        self.cache.borrow_mut().get_or_insert_with(|| self.to_string().into()).clone()
+    }
 }
 
 impl fmt::Debug for String {
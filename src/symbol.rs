@@ -0,0 +1,34 @@
+use crate::*;
+use std::fmt;
+use std::string::String as StdString;
+
+/// Reference to a JavaScript symbol, a unique value usable as an object property key.
+#[derive(Clone)]
+pub struct Symbol {
+    pub(crate) mv8: MiniV8,
+    pub(crate) handle: v8::Global<v8::Symbol>,
+}
+
+impl Symbol {
+    /// Returns the symbol's description, or `None` if it was created without one.
+    pub fn description(&self) -> Option<StdString> {
+        self.mv8.scope(|scope| {
+            let symbol = v8::Local::new(scope, self.handle.clone());
+            let description = symbol.description(scope);
+            if description.is_undefined() {
+                None
+            } else {
+                description.to_string(scope).map(|d| d.to_rust_string_lossy(scope))
+            }
+        })
+    }
+}
+
+impl fmt::Debug for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.description() {
+            Some(description) => write!(f, "Symbol({})", description),
+            None => write!(f, "Symbol()"),
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use crate::*;
+use std::string::String as StdString;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+struct CappedAllocator {
+    used: Arc<AtomicUsize>,
+    cap: usize,
+}
+
+impl BufferAllocator for CappedAllocator {
+    fn allocate(&self, len: usize) -> bool {
+        let result = self.used.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |used| {
+            Some(used + len).filter(|&total| total <= self.cap)
+        });
+        result.is_ok()
+    }
+
+    fn free(&self, len: usize) {
+        self.used.fetch_sub(len, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn capped_allocator() {
+    let used = Arc::new(AtomicUsize::new(0));
+    let allocator = CappedAllocator { used: used.clone(), cap: 1 << 20 };
+    let mv8 = MiniV8::new_with_allocator(Box::new(allocator));
+
+    let buffer: ArrayBuffer = mv8.eval("new ArrayBuffer(1024)").unwrap();
+    assert_eq!(buffer.byte_length(), 1024);
+    assert!(used.load(Ordering::SeqCst) >= 1024);
+
+    match mv8.eval::<_, Value>("new ArrayBuffer(2 << 20)") {
+        Err(Error::Value(Value::Object(error))) => {
+            assert_eq!(error.get::<_, StdString>("name").unwrap(), "RangeError");
+        },
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert!(used.load(Ordering::SeqCst) <= 1 << 20);
+}
@@ -14,6 +14,27 @@ fn set_get() {
     assert!(array.get::<Value>(3).unwrap().is_undefined());
 }
 
+#[test]
+fn try_get_set() {
+    let mv8 = MiniV8::new();
+
+    let array = mv8.create_array();
+    array.try_set(0, 123).unwrap();
+    array.try_set(1, 456).unwrap();
+    array.try_set(0, 789).unwrap();
+    assert_eq!(array.try_get::<usize>(0).unwrap(), 789);
+    assert_eq!(array.try_get::<usize>(1).unwrap(), 456);
+    match array.try_get::<Value>(2) {
+        Err(Error::OutOfBounds { index: 2, len: 2 }) => {},
+        other => panic!("unexpected result: {:?}", other),
+    }
+    match array.try_set(3, 0) {
+        Err(Error::OutOfBounds { index: 3, len: 2 }) => {},
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert_eq!(array.len(), 2);
+}
+
 #[test]
 fn len() {
     let mv8 = MiniV8::new();
@@ -56,3 +77,194 @@ fn elements() {
     let list: Result<Vec<usize>> = array.elements().collect();
     assert_eq!(list.unwrap(), vec![0, 1, 0, 3, 4]);
 }
+
+#[test]
+fn into_iter_values() {
+    let mv8 = MiniV8::new();
+
+    let array: Array = mv8.eval(r#"
+        class Reversed extends Array {
+            *[Symbol.iterator]() {
+                for (let i = this.length - 1; i >= 0; i--) yield this[i];
+            }
+        }
+        Reversed.from([1, 2, 3])
+    "#).unwrap();
+    let values: Result<Vec<Value>> = array.clone().into_iter_values().collect();
+    let values: Vec<f64> = values.unwrap().iter().filter_map(Value::as_number).collect();
+    assert_eq!(values, vec![3.0, 2.0, 1.0]);
+    let elements: Result<Vec<usize>> = array.elements().collect();
+    assert_eq!(elements.unwrap(), vec![1, 2, 3]);
+
+    let array: Array = mv8.eval(r#"
+        const array = [1, 2];
+        array[Symbol.iterator] = function*() { yield 'a'; throw new Error('oops'); };
+        array
+    "#).unwrap();
+    let mut values = array.into_iter_values();
+    assert_eq!(values.next().unwrap().unwrap().as_string().unwrap().to_string(), "a");
+    assert!(values.next().unwrap().is_err());
+    assert!(values.next().is_none());
+}
+
+#[test]
+fn create_array_from() {
+    let mv8 = MiniV8::new();
+    let array = mv8.create_array_from((1..=3).map(|i| i * 10)).unwrap();
+    assert_eq!(array.len(), 3);
+    let elements: Vec<u32> = array.elements().collect::<Result<_>>().unwrap();
+    assert_eq!(elements, vec![10, 20, 30]);
+    assert_eq!(mv8.create_array_from(Vec::<Value>::new()).unwrap().len(), 0);
+
+    struct Failing;
+
+    impl ToValue for Failing {
+        fn to_value(self, _mv8: &MiniV8) -> Result<Value> {
+            Err(Error::ToJsConversionError { from: "Failing", to: "value" })
+        }
+    }
+
+    match mv8.create_array_from(vec![Failing]) {
+        Err(Error::ToJsConversionError { from: "Failing", to: "value" }) => {},
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn try_create_array_from() {
+    let mv8 = MiniV8::new();
+
+    let array = mv8.try_create_array_from(vec![Ok(1), Ok(2), Ok(3)]).unwrap();
+    let list: Result<Vec<usize>> = array.elements().collect();
+    assert_eq!(list.unwrap(), vec![1, 2, 3]);
+
+    let mut consumed = 0;
+    let iter = (0..5).map(|i| {
+        consumed += 1;
+        if i == 2 { Err(Error::ExternalError("bad element".into())) } else { Ok(i) }
+    });
+    match mv8.try_create_array_from(iter) {
+        Err(Error::ExternalError(e)) => assert_eq!(e.to_string(), "bad element"),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert_eq!(consumed, 3);
+}
+
+#[test]
+fn zip_unzip() {
+    let mv8 = MiniV8::new();
+    let numbers: Array = mv8.eval("[1, 2, 3]").unwrap();
+    let letters: Array = mv8.eval("['a', 'b']").unwrap();
+    let zipped = numbers.zip(&letters).unwrap();
+    assert_eq!(zipped.len(), 2);
+    let pair: Array = zipped.get(1).unwrap();
+    assert_eq!(pair.get::<u32>(0).unwrap(), 2);
+    assert_eq!(pair.get::<StdString>(1).unwrap(), "b");
+
+    let (numbers, letters) = zipped.unzip().unwrap();
+    let numbers: Vec<u32> = numbers.elements().collect::<Result<_>>().unwrap();
+    let letters: Vec<StdString> = letters.elements().collect::<Result<_>>().unwrap();
+    assert_eq!(numbers, vec![1, 2]);
+    assert_eq!(letters, vec!["a", "b"]);
+
+    let invalid: Array = mv8.eval("[[1, 2], 3]").unwrap();
+    assert!(invalid.unzip().is_err());
+}
+
+#[test]
+fn slice_splice() {
+    let mv8 = MiniV8::new();
+    let array: Array = mv8.eval("[0, 1, 2, 3, 4]").unwrap();
+    let to_vec = |array: Array| array.elements().collect::<Result<Vec<u32>>>().unwrap();
+    assert_eq!(to_vec(array.slice(1, Some(3)).unwrap()), vec![1, 2]);
+    assert_eq!(to_vec(array.slice(-2, None).unwrap()), vec![3, 4]);
+    assert_eq!(to_vec(array.slice(0, Some(-4)).unwrap()), vec![0]);
+    assert_eq!(array.len(), 5);
+
+    let items = Values::from_vec(vec![Value::Number(10.0), Value::Number(11.0)]);
+    let removed = array.splice(1, 3, items).unwrap();
+    assert_eq!(to_vec(removed), vec![1, 2, 3]);
+    assert_eq!(to_vec(array), vec![0, 10, 11, 4]);
+}
+
+#[test]
+fn set_length() {
+    let mv8 = MiniV8::new();
+    let array: Array = mv8.eval("[1, 2, 3]").unwrap();
+    array.set_length(5).unwrap();
+    assert_eq!(array.len(), 5);
+    assert!(array.get::<Value>(4).unwrap().is_undefined());
+    array.set_length(1).unwrap();
+    assert_eq!(array.len(), 1);
+    assert!(array.get::<Value>(1).unwrap().is_undefined());
+    array.set_length(0).unwrap();
+    assert_eq!(array.len(), 0);
+
+    let frozen: Array = mv8.eval("Object.freeze([1, 2, 3])").unwrap();
+    match frozen.set_length(0) {
+        Err(Error::Value(Value::Object(error))) => {
+            assert_eq!(error.get::<_, StdString>("name").unwrap(), "TypeError");
+        },
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert_eq!(frozen.len(), 3);
+}
+
+#[test]
+fn index_of_and_contains() {
+    let mv8 = MiniV8::new();
+    let object = Value::Object(mv8.create_object());
+    let array = mv8.create_array_from(vec![
+        Value::Number(1.0),
+        Value::String(mv8.create_string("two")),
+        object.clone(),
+        Value::Number(f64::NAN),
+    ]).unwrap();
+
+    assert_eq!(array.index_of(&Value::Number(1.0)).unwrap(), 0);
+    assert_eq!(array.index_of(&Value::String(mv8.create_string("two"))).unwrap(), 1);
+    assert_eq!(array.index_of(&object).unwrap(), 2);
+    assert_eq!(array.index_of(&Value::Object(mv8.create_object())).unwrap(), -1);
+    assert_eq!(array.index_of(&Value::String(mv8.create_string("1"))).unwrap(), -1);
+
+    assert!(array.contains(&object).unwrap());
+    assert!(!array.contains(&Value::Undefined).unwrap());
+    assert_eq!(array.index_of(&Value::Number(f64::NAN)).unwrap(), -1);
+    assert!(array.contains(&Value::Number(f64::NAN)).unwrap());
+}
+
+#[test]
+fn to_vec() {
+    let mv8 = MiniV8::new();
+    let array: Array = mv8.eval("['1', '2', '3']").unwrap();
+    assert_eq!(array.to_vec::<u32>().unwrap(), vec![1, 2, 3]);
+    assert_eq!(array.to_vec::<StdString>().unwrap(), vec!["1", "2", "3"]);
+    assert!(mv8.create_array().to_vec::<u32>().unwrap().is_empty());
+
+    let array: Array = mv8.eval("['1', 'two', '3']").unwrap();
+    match array.to_vec::<u32>() {
+        Err(Error::FromJsConversionError { from: "string", to: "u32" }) => {},
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn deep_equals() {
+    let mv8 = MiniV8::new();
+    let a: Array = mv8.eval("[1, 'two', [3, { four: 4 }], NaN]").unwrap();
+    let b: Array = mv8.eval("[1, 'two', [3, { four: 4 }], NaN]").unwrap();
+    assert!(a.deep_equals(&b).unwrap());
+    assert!(!a.ptr_eq(&b));
+    assert_eq!(a, b);
+
+    let c: Array = mv8.eval("[1, 'two', [3, { four: '4' }], NaN]").unwrap();
+    let d: Array = mv8.eval("[1, 'two', [3, { four: 4 }]]").unwrap();
+    assert!(!a.deep_equals(&c).unwrap());
+    assert!(!a.deep_equals(&d).unwrap());
+    assert_ne!(a, c);
+    assert_ne!(a, d);
+
+    let throwing: Array = mv8.eval("Object.defineProperty([1], 0, { get() { throw 1; } })")
+        .unwrap();
+    assert_ne!(throwing, mv8.eval::<_, Array>("[1]").unwrap());
+}
@@ -0,0 +1,29 @@
+use crate::*;
+
+#[test]
+fn from_vec() {
+    let mv8 = MiniV8::new();
+    let buffer = mv8.create_array_buffer_from(vec![1, 2, 3, 4]);
+    assert_eq!(buffer.byte_length(), 4);
+    assert_eq!(buffer.to_vec(), vec![1, 2, 3, 4]);
+
+    let sum: Function = mv8.eval("b => new Uint8Array(b).reduce((a, x) => a + x, 0)").unwrap();
+    assert_eq!(sum.call::<_, usize>((buffer,)).unwrap(), 10);
+}
+
+#[test]
+fn from_script() {
+    let mv8 = MiniV8::new();
+    let value: Value = mv8.eval("new Uint8Array([5, 6, 7]).buffer").unwrap();
+    assert!(value.is_array_buffer());
+    assert_eq!(value.as_array_buffer().unwrap().to_vec(), vec![5, 6, 7]);
+}
+
+#[test]
+fn round_trip_through_object() {
+    let mv8 = MiniV8::new();
+    let object = mv8.create_object();
+    object.set("data", mv8.create_array_buffer_from(vec![9; 16])).unwrap();
+    let buffer: ArrayBuffer = object.get("data").unwrap();
+    assert_eq!(buffer.to_vec(), vec![9; 16]);
+}
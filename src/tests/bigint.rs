@@ -0,0 +1,43 @@
+use crate::*;
+
+#[test]
+fn from_script() {
+    let mv8 = MiniV8::new();
+    let value: Value = mv8.eval("10n ** 30n").unwrap();
+    assert!(value.is_bigint());
+    assert_eq!(format!("{:?}", value), "1000000000000000000000000000000n");
+    assert_eq!(value.as_bigint().unwrap().to_i128(), Some(10i128.pow(30)));
+
+    let value: i128 = mv8.eval("-(2n ** 127n)").unwrap();
+    assert_eq!(value, i128::MIN);
+    let value: u128 = mv8.eval("2n ** 128n - 1n").unwrap();
+    assert_eq!(value, u128::MAX);
+}
+
+#[test]
+fn round_trip() {
+    let mv8 = MiniV8::new();
+    let add_one: Function = mv8.eval("x => x + 1n").unwrap();
+    let value: i128 = add_one.call((-5i128,)).unwrap();
+    assert_eq!(value, -4);
+    let value: u128 = add_one.call((u64::MAX as u128,)).unwrap();
+    assert_eq!(value, u64::MAX as u128 + 1);
+    let is_bigint: bool = mv8.create_function(|inv| {
+        Ok(inv.args.get(0).is_bigint())
+    }).call((0i128,)).unwrap();
+    assert!(is_bigint);
+}
+
+#[test]
+fn out_of_range() {
+    let mv8 = MiniV8::new();
+    match mv8.eval::<_, i128>("2n ** 127n") {
+        Err(Error::FromJsConversionError { from: "bigint", to: "i128" }) => {},
+        other => panic!("unexpected result: {:?}", other),
+    }
+    match mv8.eval::<_, u128>("-1n") {
+        Err(Error::FromJsConversionError { from: "bigint", to: "u128" }) => {},
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert!(mv8.eval::<_, BigInt>("2n ** 200n").unwrap().to_u128().is_none());
+}
@@ -1,5 +1,9 @@
 use crate::*;
 use std::collections::{BTreeMap, HashMap, BTreeSet, HashSet};
+use std::rc::Rc;
+use std::string::String as StdString;
+use std::sync::Arc;
+use std::time::Duration;
 
 #[test]
 fn option() {
@@ -24,6 +28,22 @@ fn option() {
     assert_eq!(num_zero, 0);
 }
 
+#[test]
+fn smart_pointers() {
+    let mv8 = MiniV8::new();
+
+    let value = Box::new(123i32).to_value(&mv8).unwrap();
+    assert_eq!(value.as_number(), Some(123.0));
+    let boxed: Box<i32> = FromValue::from_value(value, &mv8).unwrap();
+    assert_eq!(*boxed, 123);
+
+    let shared = Rc::new(vec![1, 2]);
+    let value = shared.clone().to_value(&mv8).unwrap();
+    assert_eq!(value.into::<Vec<i32>>(&mv8).unwrap(), vec![1, 2]);
+    let value = Arc::new("abc").to_value(&mv8).unwrap();
+    assert_eq!(value.into::<StdString>(&mv8).unwrap(), "abc");
+}
+
 #[test]
 fn variadic() {
     let mv8 = MiniV8::new();
@@ -56,7 +76,7 @@ fn tuple() {
 
     type VariadicTuple = (bool, Variadic<bool>);
     let (a, var): VariadicTuple = FromValues::from_values(values.clone(), &mv8).unwrap();
-    assert_eq!(true, a);
+    assert!(a);
     assert_eq!(*var, vec![false, true]);
 
     type VariadicOver = (bool, bool, bool, bool, Variadic<bool>);
@@ -97,6 +117,56 @@ fn btree_map() {
     assert_eq!(list, vec![(1, 2), (3, 4), (5, 6)]);
 }
 
+#[test]
+fn btree_map_string_keys() {
+    let mut map = BTreeMap::new();
+    map.insert("b".to_owned(), 2.5);
+    map.insert("a".to_owned(), 1.5);
+    map.insert("c".to_owned(), 3.5);
+
+    let mv8 = MiniV8::new();
+    let value = map.clone().to_value(&mv8).unwrap();
+    let keys: Vec<StdString> = value.as_object().unwrap().keys(false).unwrap().elements()
+        .collect::<Result<_>>().unwrap();
+    assert_eq!(keys, vec!["a", "b", "c"]);
+    let round_trip: BTreeMap<StdString, f64> = value.into(&mv8).unwrap();
+    assert_eq!(round_trip, map);
+}
+
+#[test]
+fn named_fields() {
+    let mv8 = MiniV8::new();
+    let value = NamedFields((("x", 1), ("label", "origin"))).to_value(&mv8).unwrap();
+    let object = value.as_object().unwrap();
+    assert_eq!(object.get::<_, i32>("x").unwrap(), 1);
+    assert_eq!(object.get::<_, StdString>("label").unwrap(), "origin");
+    assert_eq!(object.keys(false).unwrap().len(), 2);
+}
+
+#[test]
+fn map_from_object() {
+    let mv8 = MiniV8::new();
+    let object: Value = mv8.eval("({ a: 1, b: 2 })").unwrap();
+    let hash_map: HashMap<StdString, u8> = object.clone().into(&mv8).unwrap();
+    assert_eq!(hash_map.len(), 2);
+    assert_eq!(hash_map["b"], 2);
+    let btree_map: BTreeMap<StdString, u8> = object.clone().into(&mv8).unwrap();
+    assert_eq!(btree_map.into_iter().collect::<Vec<_>>(), vec![
+        ("a".to_owned(), 1),
+        ("b".to_owned(), 2),
+    ]);
+
+    // Keys that can't be converted from property names surface a conversion error:
+    match object.into::<BTreeMap<Duration, u8>>(&mv8) {
+        Err(Error::FromJsConversionError { from: "string", to: "Duration" }) => {},
+        other => panic!("unexpected result: {:?}", other),
+    }
+    match mv8.eval::<_, HashMap<StdString, u8>>("[1, 2]") {
+        Err(Error::FromJsConversionError { from: "array", to: "HashMap" }) => {},
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
 #[test]
 fn vec() {
     let vec = vec![1, 2, 3];
@@ -106,6 +176,22 @@ fn vec() {
     assert_eq!(list.unwrap(), vec![1, 2, 3]);
 }
 
+#[test]
+fn slice() {
+    let mv8 = MiniV8::new();
+    let slice: &[u32] = &[1, 2, 3];
+    let array: Array = slice.to_value(&mv8).unwrap().into(&mv8).unwrap();
+    assert_eq!(array.len(), 3);
+    assert_eq!(array.get::<u32>(2).unwrap(), 3);
+    let vec: Vec<u32> = Value::Array(array).into(&mv8).unwrap();
+    assert_eq!(vec, slice);
+
+    match mv8.eval::<_, Vec<u32>>("({ length: 1, 0: 1 })") {
+        Err(Error::FromJsConversionError { from: "object", to: "Vec" }) => {},
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
 #[test]
 fn btree_set() {
     let btree_set: BTreeSet<_> = vec![1, 2, 3].into_iter().collect();
@@ -123,3 +209,90 @@ fn hash_set() {
         .unwrap().elements().collect();
     assert_eq!(list.unwrap(), vec![1, 2, 3].into_iter().collect());
 }
+
+#[cfg(feature = "chrono")]
+#[test]
+fn chrono_date_time() {
+    use chrono::{DateTime, TimeZone, Utc};
+
+    let mv8 = MiniV8::new();
+    let date_time = Utc.timestamp_millis_opt(1_600_000_000_123).unwrap();
+    let value = date_time.to_value(&mv8).unwrap();
+    assert_eq!(value.as_date(), Some(1_600_000_000_123.0));
+    assert_eq!(value.as_chrono(), Some(date_time));
+
+    // Sub-millisecond precision is truncated:
+    let precise = Utc.timestamp_opt(1_600_000_000, 123_456_789).unwrap();
+    assert_eq!(precise.to_value(&mv8).unwrap().as_date(), Some(1_600_000_000_123.0));
+
+    let from_js: DateTime<Utc> = mv8.eval("new Date(Date.UTC(2020, 0, 2, 3, 4, 5))").unwrap();
+    assert_eq!(from_js, Utc.with_ymd_and_hms(2020, 1, 2, 3, 4, 5).unwrap());
+    assert!(mv8.eval::<_, DateTime<Utc>>("new Date(NaN)").is_err());
+    assert!(mv8.eval::<_, DateTime<Utc>>("1600000000123").is_err());
+}
+
+#[test]
+fn checked() {
+    let mv8 = MiniV8::new();
+    let func = mv8.create_function(|inv| {
+        let (Checked(index),): (Checked<usize>,) = inv.args.into(&inv.mv8)?;
+        Ok(index + 1)
+    });
+    assert_eq!(func.call::<_, usize>((1,)).unwrap(), 2);
+    assert!(func.call::<_, usize>((1.5,)).is_err());
+    assert!(func.call::<_, usize>((-1,)).is_err());
+}
+
+#[test]
+fn strict_integers() {
+    let mv8 = MiniV8::new();
+    assert_eq!(mv8.eval::<_, u8>("255").unwrap(), 255);
+    assert_eq!(mv8.eval::<_, i64>("-(2 ** 53)").unwrap(), -(1 << 53));
+    assert_eq!(mv8.eval::<_, u32>("'42'").unwrap(), 42);
+    assert_eq!(mv8.eval::<_, i32>("null").unwrap(), 0);
+    for source in &["3.9", "-1", "256", "NaN", "Infinity", "'abc'", "undefined"] {
+        match mv8.eval::<_, u8>(*source) {
+            Err(Error::FromJsConversionError { to: "u8", .. }) => {},
+            other => panic!("unexpected result for {}: {:?}", source, other),
+        }
+    }
+    assert!(mv8.eval::<_, u128>("-1").is_err());
+    assert!(mv8.eval::<_, i64>("2 ** 63").is_err());
+
+    // Each type's own range applies, even beyond the range of `i64`:
+    assert_eq!(mv8.eval::<_, u64>("2 ** 63").unwrap(), 1 << 63);
+    assert!(mv8.eval::<_, u64>("2 ** 64").is_err());
+    assert_eq!(mv8.eval::<_, i128>("-(2 ** 100)").unwrap(), -(1 << 100));
+    assert_eq!(mv8.eval::<_, u128>("2 ** 127").unwrap(), 1 << 127);
+
+    assert_eq!(Value::Number(-7.0).coerce_integer(&mv8).unwrap(), -7);
+    assert!(Value::Number(0.5).coerce_integer(&mv8).is_err());
+    let throwing: Value = mv8.eval("({ valueOf() { throw new Error('oops'); } })").unwrap();
+    match throwing.into::<u32>(&mv8) {
+        Err(Error::Value(_)) => {},
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    // Float targets stay lenient:
+    assert_eq!(mv8.eval::<_, f64>("3.9").unwrap(), 3.9);
+    assert!(mv8.eval::<_, f32>("NaN").unwrap().is_nan());
+}
+
+#[test]
+fn i128_u128() {
+    let mv8 = MiniV8::new();
+    let identity: Function = mv8.eval("x => x").unwrap();
+
+    let big: u128 = (1 << 100) + 12345;
+    let value = big.to_value(&mv8).unwrap();
+    assert!(value.is_bigint());
+    assert_eq!(identity.call::<_, u128>((big,)).unwrap(), big);
+
+    let negative: i128 = -(1 << 100) - 6789;
+    assert_eq!(identity.call::<_, i128>((negative,)).unwrap(), negative);
+    let is_exact: bool = mv8.create_function(move |inv| {
+        let (value,): (BigInt,) = inv.args.into(&inv.mv8)?;
+        Ok(value.to_i128() == Some(negative))
+    }).call((negative,)).unwrap();
+    assert!(is_exact);
+}
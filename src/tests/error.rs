@@ -0,0 +1,46 @@
+use crate::*;
+use std::string::String as StdString;
+
+#[test]
+fn thrown_primitive() {
+    let mv8 = MiniV8::new();
+
+    let error = mv8.eval::<_, Value>("throw 42").unwrap_err();
+    assert_eq!(error.thrown_primitive().and_then(|v| v.as_number()), Some(42.0));
+    assert_eq!(error.to_string(), "JavaScript runtime error (number: 42)");
+    assert_eq!(error.to_value(&mv8).as_number(), Some(42.0));
+
+    let error = mv8.eval::<_, Value>("throw 'oops'").unwrap_err();
+    let thrown = error.thrown_primitive().and_then(|v| v.as_string()).map(|s| s.to_string());
+    assert_eq!(thrown.as_deref(), Some("oops"));
+    assert_eq!(error.to_string(), r#"JavaScript runtime error (string: "oops")"#);
+
+    let error = mv8.eval::<_, Value>("throw new Error('oops')").unwrap_err();
+    assert!(error.thrown_primitive().is_none());
+    assert_eq!(error.to_string(), "JavaScript runtime error (object)");
+    assert!(Error::Timeout.thrown_primitive().is_none());
+}
+
+#[test]
+fn to_value_native_error() {
+    let mv8 = MiniV8::new();
+    let func = mv8.create_function(|inv| {
+        let (callback,): (Function,) = inv.args.into(&inv.mv8)?;
+        callback.call::<_, Value>(())
+    });
+    mv8.global().set("f", func).unwrap();
+    let result: Object = mv8.eval(r#"
+        try { f(42); } catch (e) {
+            ({ isTypeError: e instanceof TypeError, stack: e.stack, message: e.message })
+        }
+    "#).unwrap();
+    assert!(result.get::<_, bool>("isTypeError").unwrap());
+    let message: StdString = result.get("message").unwrap();
+    assert_eq!(message, "error converting JavaScript number to Function");
+    let stack: StdString = result.get("stack").unwrap();
+    assert!(stack.starts_with("TypeError: error converting JavaScript number to Function"));
+
+    let value = Error::Timeout.to_value(&mv8);
+    let error: Function = mv8.eval("Error").unwrap();
+    assert!(value.instance_of(&error, &mv8).unwrap());
+}
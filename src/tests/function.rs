@@ -25,7 +25,7 @@ fn js_constructor() {
 fn rust_function() {
     fn add(inv: Invocation) -> Result<usize> {
         let (a, b): (usize, usize) = inv.args.into(&inv.mv8)?;
-        return Ok(a + b);
+        Ok(a + b)
     }
 
     let mv8 = MiniV8::new();
@@ -85,8 +85,11 @@ fn return_unit() {
     let func = mv8.create_function(|_| Ok(()));
     let _: () = func.call(()).unwrap();
     let _: () = func.call((123,)).unwrap();
-    let number_cast: usize = func.call(()).unwrap();
-    assert_eq!(number_cast, 0);
+    // `undefined` is `NaN` as a number, so it isn't silently converted to an integer:
+    match func.call::<_, usize>(()) {
+        Err(Error::FromJsConversionError { from: "undefined", to: "usize" }) => {},
+        other => panic!("unexpected result: {:?}", other),
+    }
 }
 
 #[test]
@@ -103,7 +106,7 @@ fn rust_closure_mut_callback_error() {
             // Produce a mutable reference:
             let r = v.as_mut().unwrap();
             // Whoops, this will recurse into the function and produce another mutable reference!
-            mv8.global().get::<_, Function>("f")?.call((true,))?;
+            mv8.global().get::<_, Function>("f")?.call::<_, ()>((true,))?;
             println!("Should not get here, mutable aliasing has occurred!");
             println!("value at {:p}", r as *mut _);
             println!("value is {}", r);
@@ -127,7 +130,7 @@ fn number_this() {
     fn add(inv: Invocation) -> Result<f64> {
         let this: f64 = inv.this.into(&inv.mv8)?;
         let (acc,): (f64,) = inv.args.into(&inv.mv8)?;
-        return Ok(this + acc);
+        Ok(this + acc)
     }
 
     let mv8 = MiniV8::new();
@@ -144,3 +147,300 @@ fn number_this() {
     let value: f64 = mv8.eval("add(5)").unwrap();
     assert!(value.is_nan());
 }
+
+#[test]
+fn weak_owner() {
+    let mv8 = MiniV8::new();
+    let owner = mv8.create_object();
+    owner.set("name", "owner").unwrap();
+
+    let weak_owner = Value::Object(owner.clone()).downgrade();
+    let handler = mv8.create_function_weak(weak_owner, |owner, inv| {
+        match owner {
+            Some(owner) => owner.into::<Object>(&inv.mv8)?.get("name"),
+            None => Ok(Value::Null),
+        }
+    });
+    let name: StdString = handler.call(()).unwrap();
+    assert_eq!(name, "owner");
+
+    drop(owner);
+    mv8.low_memory_notification();
+    let name: Option<StdString> = handler.call(()).unwrap();
+    assert_eq!(name, None);
+}
+
+#[test]
+fn rest_from() {
+    let mv8 = MiniV8::new();
+    // Implements `(separator, ...parts) => parts.join(separator)`:
+    let join = mv8.create_function(|inv| {
+        let separator: StdString = inv.args.from(&inv.mv8, 0)?;
+        let parts: Variadic<StdString> = inv.rest_from(1).into(&inv.mv8)?;
+        Ok(parts.join(&separator))
+    });
+    mv8.global().set("join", join).unwrap();
+    assert_eq!(mv8.eval::<_, StdString>("join('-', 'a', 'b', 'c')").unwrap(), "a-b-c");
+    assert_eq!(mv8.eval::<_, StdString>("join('-')").unwrap(), "");
+    assert_eq!(mv8.eval::<_, StdString>("join()").unwrap(), "");
+}
+
+#[test]
+fn construct_call() {
+    let mv8 = MiniV8::new();
+    let func = mv8.create_function(|inv| {
+        if inv.is_construct_call {
+            let this = inv.this.as_object().unwrap().clone();
+            this.set("target", inv.new_target)?;
+            Ok(Value::Object(this))
+        } else {
+            assert!(inv.new_target.is_undefined());
+            Ok(Value::String(inv.mv8.create_string("called")))
+        }
+    });
+    mv8.global().set("Point", func).unwrap();
+    let called: StdString = mv8.eval("Point()").unwrap();
+    assert_eq!(called, "called");
+    assert!(mv8.eval::<_, bool>("new Point().target === Point").unwrap());
+}
+
+#[test]
+fn name_and_arity() {
+    let mv8 = MiniV8::new();
+    let func: Function = mv8.eval("function foo(a, b, c) {}; foo").unwrap();
+    assert_eq!(func.name().unwrap().to_string(), "foo");
+    assert_eq!(func.arity().unwrap(), 3);
+
+    let func = mv8.create_function(|_| Ok(Value::Undefined));
+    assert_eq!(func.name().unwrap().to_string(), "");
+    assert_eq!(func.arity().unwrap(), 0);
+}
+
+#[test]
+fn debug() {
+    let mv8 = MiniV8::new();
+    let func: Function = mv8.eval("function foo(a, b) {}; foo").unwrap();
+    assert_eq!(format!("{:?}", func), "<function foo(2)>");
+    let func = mv8.create_function(|_| Ok(()));
+    assert_eq!(format!("{:?}", func), "<function <anonymous>(0)>");
+    let func: Function = mv8.eval(r#"
+        const f = function(a) {};
+        Object.defineProperty(f, 'name', { get() { throw new Error('oops'); } });
+        f
+    "#).unwrap();
+    assert_eq!(format!("{:?}", func), "<function <anonymous>(1)>");
+}
+
+#[test]
+fn apply() {
+    let mv8 = MiniV8::new();
+    let func: Function = mv8.eval("(function() { return this.base + arguments.length; })").unwrap();
+    let this: Value = mv8.eval("({ base: 100 })").unwrap();
+    let args = (0..5).map(|n| Value::Number(f64::from(n))).collect();
+    assert_eq!(func.apply::<u32>(this, args).unwrap(), 105);
+}
+
+#[test]
+fn reflect_apply() {
+    let mv8 = MiniV8::new();
+    let func: Function = mv8.eval(r#"
+        function sum(a, b) { return this.base + a + b; }
+        sum.apply = () => 'tampered';
+        Function.prototype.apply = () => 'tampered';
+        Reflect.apply = () => 'tampered';
+        sum
+    "#).unwrap();
+    let this: Value = mv8.eval("({ base: 100 })").unwrap();
+    let args: Array = mv8.eval("[20, 3]").unwrap();
+    let result = func.reflect_apply(this, &args).unwrap();
+    assert_eq!(result.as_number(), Some(123.0));
+}
+
+#[test]
+fn revocable() {
+    let mv8 = MiniV8::new();
+    let (func, handle) = mv8.create_revocable_function(|inv| {
+        let (a, b): (i32, i32) = inv.args.into(&inv.mv8)?;
+        Ok(a + b)
+    });
+    mv8.global().set("add", func.clone()).unwrap();
+    assert_eq!(mv8.eval::<_, i32>("add(1, 2)").unwrap(), 3);
+
+    handle.revoke();
+    assert!(handle.is_revoked());
+    match func.call::<_, Value>((1, 2)) {
+        Err(Error::Value(Value::Object(error))) => {
+            assert_eq!(error.get::<_, StdString>("name").unwrap(), "TypeError");
+        },
+        other => panic!("unexpected result: {:?}", other),
+    }
+    let message: StdString = mv8.eval(r#"
+        try { add(1, 2); 'not thrown' } catch (e) { `${e.name}: ${e.message}` }
+    "#).unwrap();
+    assert_eq!(message, "TypeError: function has been revoked");
+}
+
+#[test]
+fn recursive() {
+    let mv8 = MiniV8::new();
+    let factorial = mv8.create_function_recursive(|this, inv| {
+        let (n,): (u32,) = inv.args.into(&inv.mv8)?;
+        if n <= 1 {
+            Ok(1)
+        } else {
+            Ok(n * this.call::<_, u32>((n - 1,))?)
+        }
+    });
+    assert_eq!(factorial.call::<_, u32>((5,)).unwrap(), 120);
+
+    let identity = mv8.create_function_recursive(|this, _| Ok(this.clone()));
+    mv8.global().set("identity", identity).unwrap();
+    assert!(mv8.eval::<_, bool>("identity() === identity").unwrap());
+}
+
+#[test]
+fn construct_apply() {
+    let mv8 = MiniV8::new();
+    let date: Function = mv8.eval("Date").unwrap();
+    let args: Array = mv8.eval("[Date.UTC(2020, 0, 1)]").unwrap();
+    let value = date.construct_apply(&args).unwrap();
+    assert_eq!(value.as_date(), Some(1577836800000.0));
+
+    let args: Array = mv8.eval("[2020, 0, 1]").unwrap();
+    let value = date.construct_apply(&args).unwrap();
+    mv8.global().set("constructed", value).unwrap();
+    assert!(mv8.eval::<_, bool>("constructed.getFullYear() === 2020").unwrap());
+}
+
+#[test]
+fn max_callback_depth() {
+    let mv8 = MiniV8::new();
+    mv8.set_max_callback_depth(Some(10));
+    let descend = mv8.create_function(|inv| {
+        let (n,): (u32,) = inv.args.into(&inv.mv8)?;
+        inv.mv8.eval::<_, u32>(format!("descend({}) + 1", n + 1))
+    });
+    mv8.global().set("descend", descend).unwrap();
+
+    // The recursion never terminates on its own, so the guard must stop it:
+    let error: Object = mv8.eval("try { descend(0); } catch (e) { e }").unwrap();
+    let message: StdString = error.get("message").unwrap();
+    assert_eq!(message, "maximum callback depth exceeded");
+
+    // The depth unwinds properly, so shallow calls still work afterward:
+    let shallow = mv8.create_function(|_| Ok(1));
+    mv8.global().set("shallow", shallow).unwrap();
+    assert_eq!(mv8.eval::<_, u32>("shallow()").unwrap(), 1);
+
+    mv8.set_max_callback_depth(None);
+    let depth = mv8.create_function(|inv| {
+        let (n,): (u32,) = inv.args.into(&inv.mv8)?;
+        if n == 0 { Ok(0) } else { inv.mv8.eval::<_, u32>(format!("depth({}) + 1", n - 1)) }
+    });
+    mv8.global().set("depth", depth).unwrap();
+    assert_eq!(mv8.eval::<_, u32>("depth(20)").unwrap(), 20);
+}
+
+#[test]
+fn many_functions() {
+    let mv8 = MiniV8::new();
+    let base = mv8.callback_count();
+
+    let create = |i: usize| mv8.create_function(move |_| Ok(i));
+    let functions: Vec<Function> = (0..10_000).map(create).collect();
+    assert_eq!(mv8.callback_count(), base + 10_000);
+    for (i, function) in functions.iter().enumerate().step_by(997) {
+        assert_eq!(function.call::<_, usize>(()).unwrap(), i);
+    }
+
+    drop(functions);
+    mv8.low_memory_notification();
+    assert_eq!(mv8.callback_count(), base);
+
+    // Freed slots are reused, and dispatch to the right function:
+    let functions: Vec<Function> = (0..10_000).map(create).collect();
+    assert_eq!(mv8.callback_count(), base + 10_000);
+    for (i, function) in functions.iter().enumerate().step_by(997) {
+        assert_eq!(function.call::<_, usize>(()).unwrap(), i);
+    }
+}
+
+#[test]
+fn panicking_callback() {
+    let mv8 = MiniV8::new();
+    let panicking = mv8.create_function(|inv| -> Result<()> {
+        let (message,): (StdString,) = inv.args.into(&inv.mv8)?;
+        panic!("{}", message);
+    });
+    mv8.global().set("panicking", panicking.clone()).unwrap();
+
+    let message: StdString = mv8.eval(r#"
+        try { panicking('plugin failed'); } catch (e) { e instanceof Error && e.message }
+    "#).unwrap();
+    assert_eq!(message, "Rust function panicked: plugin failed");
+
+    // Panics propagate to Rust as errors, and the `MiniV8` remains usable afterward:
+    assert!(panicking.call::<_, ()>(("again",)).is_err());
+    let add = mv8.create_function(|inv| {
+        let (a, b): (f64, f64) = inv.args.into(&inv.mv8)?;
+        Ok(a + b)
+    });
+    assert_eq!(add.call::<_, f64>((1, 2)).unwrap(), 3.0);
+    assert_eq!(mv8.eval::<_, u32>("1 + 1").unwrap(), 2);
+}
+
+#[test]
+fn create_class() {
+    let mv8 = MiniV8::new();
+    let constructor = mv8.create_function(|inv| {
+        let (start,): (Option<f64>,) = inv.args.into(&inv.mv8)?;
+        let this: Object = inv.this.into(&inv.mv8)?;
+        this.set("count", start.unwrap_or(0.0))
+    });
+    let increment = mv8.create_function(|inv| {
+        let this: Object = inv.this.into(&inv.mv8)?;
+        let count: f64 = this.get("count")?;
+        this.set("count", count + 1.0)?;
+        Ok(count + 1.0)
+    });
+    let counter = mv8.create_class("Counter", vec![("increment", increment)], Some(constructor))
+        .unwrap();
+    mv8.global().set("Counter", counter).unwrap();
+
+    let result: Vec<f64> = mv8.eval(r#"
+        const a = new Counter();
+        const b = new Counter(10);
+        a.increment();
+        [a.increment(), b.increment()]
+    "#).unwrap();
+    assert_eq!(result, vec![2.0, 11.0]);
+
+    let checks: Vec<bool> = mv8.eval(r#"[
+        Counter.name === 'Counter',
+        a instanceof Counter,
+        a.constructor === Counter,
+        Object.keys(a).join() === 'count',
+        a.increment === b.increment,
+    ]"#).unwrap();
+    assert!(checks.into_iter().all(|check| check));
+
+    let is_type_error: bool = mv8.eval("try { Counter(); } catch (e) { e instanceof TypeError }")
+        .unwrap();
+    assert!(is_type_error);
+
+    let empty = mv8.create_class("Empty", Vec::new(), None).unwrap();
+    let instance: Object = empty.call_new(()).unwrap();
+    assert_eq!(instance.len().unwrap(), 0);
+
+    // Constructors can return a replacement object, but not a primitive:
+    let replacement: Object = mv8.eval("({ replaced: true })").unwrap();
+    let replacement_clone = replacement.clone();
+    let constructor = mv8.create_function(move |_| Ok(replacement_clone.clone()));
+    let replacing = mv8.create_class("Replacing", Vec::new(), Some(constructor)).unwrap();
+    let instance: Object = replacing.call_new(()).unwrap();
+    assert!(instance.ptr_eq(&replacement));
+    let constructor = mv8.create_function(|_| Ok(1));
+    let primitive = mv8.create_class("Primitive", Vec::new(), Some(constructor)).unwrap();
+    mv8.global().set("Primitive", primitive).unwrap();
+    assert!(mv8.eval::<_, bool>("new Primitive() instanceof Primitive").unwrap());
+}
@@ -0,0 +1,42 @@
+use crate::*;
+use std::string::String as StdString;
+
+#[test]
+fn set_get() {
+    let mv8 = MiniV8::new();
+    let map = mv8.create_map();
+    let key = mv8.create_object();
+    map.set("a", 1).unwrap();
+    map.set(key.clone(), "object").unwrap();
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.get::<_, usize>("a").unwrap(), 1);
+    assert_eq!(map.get::<_, StdString>(key.clone()).unwrap(), "object");
+    assert!(map.get::<_, Value>(mv8.create_object()).unwrap().is_undefined());
+    assert!(map.has(key.clone()).unwrap());
+    assert!(!map.has("b").unwrap());
+}
+
+#[test]
+fn remove() {
+    let mv8 = MiniV8::new();
+    let map = mv8.create_map();
+    map.set(1, 2).unwrap();
+    assert!(map.remove(1).unwrap());
+    assert!(!map.remove(1).unwrap());
+    assert_eq!(map.len(), 0);
+}
+
+#[test]
+fn entries() {
+    let mv8 = MiniV8::new();
+    let value: Value = mv8.eval("new Map([[1, 'a'], ['b', 2]])").unwrap();
+    assert!(value.is_map());
+    let map = value.as_map().unwrap().clone();
+    let entries = map.entries::<Value, Value>().collect::<Result<Vec<_>>>().unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].0.as_number(), Some(1.0));
+    assert_eq!(entries[0].1.as_string().unwrap().to_string(), "a");
+    assert_eq!(entries[1].0.as_string().unwrap().to_string(), "b");
+    assert_eq!(entries[1].1.as_number(), Some(2.0));
+    assert_eq!(format!("{:?}", value), r#"Map { 1 => "a", "b" => 2 }"#);
+}
@@ -1,5 +1,6 @@
 use crate::*;
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::rc::Rc;
 use std::string::String as StdString;
 use std::time::Duration;
@@ -20,6 +21,200 @@ fn eval_origin() {
     assert_eq!("ReferenceError: MISSING_VAR is not defined at eval_origin:124:463", result);
 }
 
+#[test]
+fn repl_context() {
+    let mv8 = MiniV8::new();
+    let mut repl = ReplContext::new("repl");
+    repl.eval::<_, Value>(&mv8, "var a = 1;\nvar b = 2;").unwrap();
+    assert_eq!(repl.line_offset(), 2);
+    let result = repl.eval::<_, Value>(&mv8, "a + b;\nthrow new Error('boom');");
+    let stack: StdString = match result {
+        Err(Error::Value(Value::Object(error))) => error.get("stack").unwrap(),
+        other => panic!("unexpected result: {:?}", other),
+    };
+    assert!(stack.contains("at repl:4:1"), "unexpected stack: {}", stack);
+    assert_eq!(repl.line_offset(), 4);
+}
+
+#[test]
+fn script_source() {
+    let mv8 = MiniV8::new();
+    let recorded = Rc::new(RefCell::new(None));
+    let recorded_clone = recorded.clone();
+    let record = mv8.create_function(move |inv| {
+        *recorded_clone.borrow_mut() = inv.mv8.current_script_id();
+        Ok(())
+    });
+    mv8.global().set("record", record).unwrap();
+
+    let source = "record(); 1 + 1";
+    let _: Value = mv8.eval(Script {
+        source: source.to_owned(),
+        register: true,
+        ..Default::default()
+    }).unwrap();
+    let script_id = recorded.borrow().expect("no script id recorded");
+    assert_eq!(mv8.script_source(script_id).as_deref(), Some(source));
+
+    // Unregistered scripts are not retained:
+    let _: Value = mv8.eval("record()").unwrap();
+    let script_id = recorded.borrow().expect("no script id recorded");
+    assert_eq!(mv8.script_source(script_id), None);
+
+    let source = "function f() { record(); } f()";
+    let script = mv8.compile(Script {
+        source: source.to_owned(),
+        register: true,
+        ..Default::default()
+    }).unwrap();
+    script.run::<()>().unwrap();
+    let script_id = recorded.borrow().expect("no script id recorded");
+    assert_eq!(mv8.script_source(script_id).as_deref(), Some(source));
+}
+
+#[test]
+fn create_object_from_error() {
+    struct Fallible(Option<i32>);
+
+    impl ToValue for Fallible {
+        fn to_value(self, mv8: &MiniV8) -> Result<Value> {
+            match self.0 {
+                Some(value) => value.to_value(mv8),
+                None => Err(Error::ToJsConversionError { from: "Fallible", to: "number" }),
+            }
+        }
+    }
+
+    let mv8 = MiniV8::new();
+    let properties = vec![
+        ("a", Fallible(Some(1))),
+        ("b", Fallible(None)),
+        ("c", Fallible(Some(3))),
+    ];
+    match mv8.create_object_from(properties) {
+        Err(Error::PropertyError { index: 1, key: Some(key), source }) => {
+            assert_eq!(key, "b");
+            match *source {
+                Error::ToJsConversionError { from: "Fallible", to: "number" } => {},
+                other => panic!("unexpected source: {:?}", other),
+            }
+        },
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn eval_module() {
+    let mv8 = MiniV8::new();
+    let namespace = mv8.eval_module("math.js", r#"
+        export const answer = 6 * 7;
+        export default await Promise.resolve('done');
+    "#).unwrap();
+    assert_eq!(namespace.get::<_, u32>("answer").unwrap(), 42);
+    assert_eq!(namespace.get::<_, StdString>("default").unwrap(), "done");
+
+    match mv8.eval_module("throws.js", "throw new Error('oops')") {
+        Err(Error::Value(Value::Object(error))) => {
+            assert_eq!(error.get::<_, StdString>("message").unwrap(), "oops");
+        },
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert!(mv8.eval_module("imports.js", "import './missing.js';").is_err());
+}
+
+#[test]
+fn module_cache() {
+    let mv8 = MiniV8::new();
+    mv8.eval_module("counter.js", r#"
+        export let count = 0;
+        export function increment() { count++; }
+    "#).unwrap();
+    mv8.eval_module("a.js", "import { increment } from 'counter.js'; increment();").unwrap();
+    let b = mv8.eval_module("b.js", r#"
+        import { count, increment } from 'counter.js';
+        increment();
+        export const seen = count;
+    "#).unwrap();
+    assert_eq!(b.get::<_, u32>("seen").unwrap(), 2);
+
+    // Evaluating a cached module again reuses it:
+    let counter = mv8.eval_module("counter.js", "export let count = 100;").unwrap();
+    assert_eq!(counter.get::<_, u32>("count").unwrap(), 2);
+
+    mv8.clear_module_cache();
+    assert!(mv8.eval_module("c.js", "import { count } from 'counter.js';").is_err());
+    let counter = mv8.eval_module("counter.js", "export let count = 100;").unwrap();
+    assert_eq!(counter.get::<_, u32>("count").unwrap(), 100);
+}
+
+#[test]
+fn module_names_released() {
+    let mv8 = MiniV8::new();
+    mv8.eval_module("a.js", "export const a = 1;").unwrap();
+    mv8.eval_module("b.js", "import { a } from 'a.js'; export const b = a;").unwrap();
+    assert_eq!(mv8.module_name_count(), 2);
+
+    // Modules that fail to load or evaluate are not kept:
+    assert!(mv8.eval_module("c.js", "import 'missing.js';").is_err());
+    assert!(mv8.eval_module("d.js", "throw new Error('d');").is_err());
+    assert_eq!(mv8.module_name_count(), 2);
+
+    mv8.clear_module_cache();
+    assert_eq!(mv8.module_name_count(), 0);
+}
+
+#[test]
+fn eval_module_with_resolver() {
+    let mut sources = HashMap::new();
+    sources.insert("/lib/a.js", r#"
+        import { b } from './b.js';
+        export function a() { return 'a'; }
+        export const result = b();
+    "#);
+    sources.insert("/lib/b.js", r#"
+        import { a } from '/lib/a.js';
+        export function b() { return 'b' + a(); }
+    "#);
+    let resolve = |specifier: &str, referrer: &str| {
+        let name = match specifier.strip_prefix("./") {
+            Some(relative) => format!("{}/{}", &referrer[..referrer.rfind('/').unwrap()], relative),
+            None => specifier.to_owned(),
+        };
+        match sources.get(name.as_str()) {
+            Some(source) => Ok(ModuleSource { name, source: source.to_string() }),
+            None => Err(Error::ExternalError(format!("module {:?} not found", name).into())),
+        }
+    };
+
+    let mv8 = MiniV8::new();
+    let main = mv8.eval_module_with_resolver("/main.js", r#"
+        import { result } from './lib/a.js';
+        export default result;
+    "#, resolve).unwrap();
+    assert_eq!(main.get::<_, StdString>("default").unwrap(), "ba");
+    // Loaded modules are cached:
+    let b = mv8.eval_module("/lib/b.js", "").unwrap();
+    assert_eq!(b.call_prop::<_, _, StdString>("b", ()).unwrap(), "ba");
+
+    match mv8.eval_module_with_resolver("/other.js", "import './missing.js';", resolve) {
+        Err(Error::Value(Value::Object(error))) => {
+            let message: StdString = error.get("message").unwrap();
+            assert_eq!(message, r#"module "/missing.js" not found"#);
+        },
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn import_meta() {
+    let mv8 = MiniV8::new();
+    mv8.set_import_meta(|name, meta| {
+        meta.set("url", format!("file:///{}", name)).unwrap();
+    });
+    let namespace = mv8.eval_module("main.js", "export const url = import.meta.url;").unwrap();
+    assert_eq!(namespace.get::<_, StdString>("url").unwrap(), "file:///main.js");
+}
+
 #[test]
 fn eval_timeout() {
     let mv8 = MiniV8::new();
@@ -39,6 +234,162 @@ fn eval_timeout() {
     assert!(a > 0.0);
 }
 
+#[test]
+fn set_console() {
+    let mv8 = MiniV8::new();
+    let messages = Rc::new(RefCell::new(Vec::new()));
+    let sink_messages = messages.clone();
+    mv8.set_console(move |level, args| {
+        let args: Vec<StdString> = args.iter().map(|arg| format!("{:?}", arg)).collect();
+        sink_messages.borrow_mut().push((level, args.join(" ")));
+    }).unwrap();
+    mv8.eval::<_, ()>("console.log('a', 1); console.warn([true]); console.error()").unwrap();
+    assert_eq!(*messages.borrow(), vec![
+        (ConsoleLevel::Log, r#""a" 1"#.to_owned()),
+        (ConsoleLevel::Warn, "[true]".to_owned()),
+        (ConsoleLevel::Error, "".to_owned()),
+    ]);
+}
+
+#[test]
+fn eval_completion() {
+    let mv8 = MiniV8::new();
+    assert!(matches!(mv8.eval_completion("let x = 1").unwrap(), Completion::Empty));
+    assert!(matches!(mv8.eval_completion("function f() {}").unwrap(), Completion::Empty));
+    match mv8.eval_completion("undefined").unwrap() {
+        Completion::Value(value) => assert!(value.is_undefined()),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    match mv8.eval_completion("'mini-v8:empty-completion:0000000000000000'").unwrap() {
+        Completion::Value(value) => assert_eq!(value, "mini-v8:empty-completion:0000000000000000"),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    match mv8.eval_completion("x + 1").unwrap() {
+        Completion::Value(value) => assert_eq!(value.as_number(), Some(2.0)),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    // Directives still take effect:
+    assert!(mv8.eval_completion("'use strict'; undeclared = 1").is_err());
+    match mv8.eval_completion("#!/usr/bin/env node\n'done'").unwrap() {
+        Completion::Value(value) => assert_eq!(value, "done"),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert!(matches!(mv8.eval_completion("#!/usr/bin/env node").unwrap(), Completion::Empty));
+    // Positions refer to the script as given:
+    assert!(mv8.eval_completion("  MISSING_VAR").is_err());
+    let details = mv8.last_exception_details().unwrap();
+    assert_eq!(details.resource_name, None);
+    assert_eq!(details.start_column, 2);
+}
+
+#[test]
+fn eval_extract() {
+    let mv8 = MiniV8::new();
+    let source = "var a = 1; b = 'two';";
+    let values = mv8.eval_extract(source, &["a", "b", "c"], ExtractSource::Global).unwrap();
+    assert_eq!(values[0].as_number(), Some(1.0));
+    assert_eq!(values[1].as_string().unwrap().to_string(), "two");
+    assert!(values[2].is_undefined());
+
+    let values = mv8.eval_extract("({ a: 3, b: 4 })", &["b", "a"], ExtractSource::Result).unwrap();
+    let values: Vec<f64> = values.iter().filter_map(Value::as_number).collect();
+    assert_eq!(values, vec![4.0, 3.0]);
+
+    // The source is never inferred from the result:
+    let source = "a = 5; b = { x: 1 }";
+    let values = mv8.eval_extract(source, &["a", "x"], ExtractSource::Global).unwrap();
+    assert_eq!(values[0].as_number(), Some(5.0));
+    assert!(values[1].is_undefined());
+    let values = mv8.eval_extract("[6]", &["0"], ExtractSource::Result).unwrap();
+    assert_eq!(values[0].as_number(), Some(6.0));
+    assert!(mv8.eval_extract("a = 1", &["a"], ExtractSource::Result).is_err());
+}
+
+#[test]
+fn compile_run() {
+    let mv8 = MiniV8::new();
+    mv8.eval::<_, ()>("var counter = 0;").unwrap();
+    let script = mv8.compile("counter += 1").unwrap();
+    assert_eq!(script.run::<u32>().unwrap(), 1);
+    assert_eq!(script.run::<u32>().unwrap(), 2);
+    assert_eq!(mv8.eval::<_, u32>("counter").unwrap(), 2);
+
+    assert!(mv8.compile("let = ;").is_err());
+
+    let script = mv8.compile(Script {
+        source: "while (true) {}".to_owned(),
+        timeout: Some(Duration::from_millis(50)),
+        ..Default::default()
+    }).unwrap();
+    match script.run::<Value>() {
+        Err(Error::Timeout) => {},
+        other => panic!("unexpected result: {:?}", other),
+    }
+    match script.run::<Value>() {
+        Err(Error::Timeout) => {},
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn code_cache() {
+    let source = "function square(x) { return x * x; } square(12)";
+    let cache = {
+        let mv8 = MiniV8::new();
+        let script = mv8.compile(source).unwrap();
+        assert_eq!(script.run::<u32>().unwrap(), 144);
+        script.create_code_cache().unwrap()
+    };
+
+    let mv8 = MiniV8::new();
+    let (script, rejected) = mv8.compile_with_cache(source, cache.clone()).unwrap();
+    assert!(!rejected);
+    assert_eq!(script.run::<u32>().unwrap(), 144);
+
+    // Stale or invalid cache data is rejected, and the script is compiled from scratch:
+    let (script, rejected) = mv8.compile_with_cache("square(3)", cache).unwrap();
+    assert!(rejected);
+    assert_eq!(script.run::<u32>().unwrap(), 9);
+    let (script, rejected) = mv8.compile_with_cache(source, vec![1, 2, 3]).unwrap();
+    assert!(rejected);
+    assert_eq!(script.run::<u32>().unwrap(), 144);
+}
+
+#[test]
+fn bytecode_size() {
+    let mv8 = MiniV8::new();
+    let trivial = mv8.compile("1").unwrap();
+    let statements: Vec<StdString> = (0..200)
+        .map(|i| format!("var v{} = [{}, '{}', {{ k: {} }}].map(x => x);", i, i, i, i))
+        .collect();
+    let larger = mv8.compile(statements.join("\n")).unwrap();
+    assert!(larger.bytecode_size().unwrap() > trivial.bytecode_size().unwrap());
+}
+
+#[test]
+fn terminate_execution() {
+    let mv8 = MiniV8::new();
+    let handle = mv8.thread_safe_handle();
+    let thread = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(50));
+        assert!(handle.terminate_execution());
+    });
+    match mv8.eval::<_, Value>("a = 0; while (true) { a++; }") {
+        Err(Error::Timeout) => {},
+        other => panic!("unexpected result: {:?}", other),
+    }
+    thread.join().unwrap();
+
+    // Make sure we can still evaluate again:
+    let a: f64 = mv8.eval("a").unwrap();
+    assert!(a > 0.0);
+
+    let handle = mv8.thread_safe_handle();
+    handle.terminate_execution();
+    handle.cancel_terminate_execution();
+    assert_eq!(mv8.eval::<_, i32>("1 + 1").unwrap(), 2);
+}
+
 #[test]
 fn eval_wasm() {
     let mv8 = MiniV8::new();
@@ -55,7 +406,7 @@ fn eval_wasm() {
     "#);
 
     match result {
-        Ok(Value::Number(n)) if n == 7.0 => {},
+        Ok(Value::Number(7.0)) => {},
         _ => panic!("unexpected result: {:?}", result),
     }
 }
@@ -109,6 +460,29 @@ fn user_data_remove() {
     assert_eq!(*count.borrow(), 1000);
 }
 
+#[test]
+fn user_data_typed() {
+    let mv8 = MiniV8::new();
+    assert_eq!(mv8.set_user_data_typed("count", 1usize), None);
+    assert_eq!(mv8.set_user_data_typed("count", 2usize), Some(1));
+    assert_eq!(mv8.user_data_clone::<usize>("count"), Some(2));
+    assert_eq!(mv8.user_data_clone::<u32>("count"), None);
+    assert_eq!(mv8.user_data_clone::<usize>("no-exist"), None);
+    // A previous value of another type is not returned:
+    assert_eq!(mv8.set_user_data_typed("count", "two"), None);
+
+    let shared = Rc::new(RefCell::new(Vec::<StdString>::new()));
+    mv8.set_user_data("log", shared.clone());
+    let log = mv8.create_function(|inv| {
+        let log = inv.mv8.user_data_clone::<Rc<RefCell<Vec<StdString>>>>("log").unwrap();
+        let (message,): (StdString,) = inv.args.into(&inv.mv8)?;
+        log.borrow_mut().push(message);
+        Ok(())
+    });
+    log.call::<_, ()>(("hello",)).unwrap();
+    assert_eq!(*shared.borrow(), vec!["hello".to_string()]);
+}
+
 struct TestUserData {
     count: Rc<RefCell<usize>>,
 }
@@ -133,3 +507,250 @@ fn make_test_user_data() -> (Rc<RefCell<usize>>, TestUserData) {
     let count = Rc::new(RefCell::new(0));
     (count.clone(), TestUserData { count })
 }
+
+#[test]
+fn number_formatting() {
+    let mv8 = MiniV8::new();
+    let expected: StdString = mv8.eval("(1.005).toFixed(2)").unwrap();
+    assert_eq!(mv8.to_fixed(1.005, 2).unwrap(), expected);
+    assert_eq!(mv8.to_fixed(1.005, 2).unwrap(), "1.00");
+    assert_eq!(mv8.to_precision(123.456, 4).unwrap(), "123.5");
+    assert!(mv8.to_fixed(1.0, 101).is_err());
+
+    // Replacing the JavaScript built-ins does not affect formatting:
+    let _: () = mv8.eval("Number.prototype.toFixed = () => 'oops'").unwrap();
+    assert_eq!(mv8.to_fixed(2.5, 1).unwrap(), "2.5");
+}
+
+#[test]
+fn eval_sandboxed_timeout() {
+    let mv8 = MiniV8::new();
+    let result = mv8.eval_sandboxed::<_, Value>("while (true) {}", ResourceLimits {
+        timeout: Some(Duration::from_millis(50)),
+        ..Default::default()
+    });
+    match result {
+        Err(Error::Timeout) => {},
+        _ => panic!("unexpected result: {:?}", result),
+    }
+    assert_eq!(mv8.eval::<_, usize>("1 + 1").unwrap(), 2);
+}
+
+#[test]
+fn eval_sandboxed_heap_limit() {
+    let mv8 = MiniV8::new();
+    let result = mv8.eval_sandboxed::<_, Value>(r#"
+        let chunks = [];
+        while (true) {
+            chunks.push(new Array(100000).fill(1.5));
+        }
+    "#, ResourceLimits {
+        max_heap_size: Some(32 * 1024 * 1024),
+        ..Default::default()
+    });
+    match result {
+        Err(Error::HeapLimit) => {},
+        _ => panic!("unexpected result: {:?}", result),
+    }
+    assert_eq!(mv8.eval::<_, usize>("1 + 1").unwrap(), 2);
+}
+
+#[test]
+fn eval_sandboxed_stack_limit() {
+    let mv8 = MiniV8::new();
+    let source = "function f(n) { return n === 0 ? 0 : 1 + f(n - 1); } f(5000)";
+    assert_eq!(mv8.eval::<_, usize>(source).unwrap(), 5000);
+
+    let result = mv8.eval_sandboxed::<_, Value>(source, ResourceLimits {
+        max_stack_size: Some(16 * 1024),
+        ..Default::default()
+    });
+    match result {
+        Err(Error::StackOverflow(Value::Object(error))) => {
+            assert_eq!(error.get::<_, StdString>("name").unwrap(), "RangeError");
+        },
+        _ => panic!("unexpected result: {:?}", result),
+    }
+
+    // The original stack limit is restored afterwards:
+    assert_eq!(mv8.eval::<_, usize>(source).unwrap(), 5000);
+}
+
+#[test]
+fn stack_overflow() {
+    let mv8 = MiniV8::new();
+    match mv8.eval::<_, Value>("function f() { return f() + 1; } f()") {
+        Err(Error::StackOverflow(Value::Object(error))) => {
+            assert_eq!(error.get::<_, StdString>("name").unwrap(), "RangeError");
+        },
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    // Other errors, including other `RangeError`s, are not classified as stack overflows:
+    match mv8.eval::<_, Value>("new Array(-1)") {
+        Err(Error::Value(_)) => {},
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    // Stack overflows propagate through Rust functions as the original `RangeError`:
+    let recurse = mv8.create_function(|inv| inv.mv8.eval::<_, Value>("recurse()"));
+    mv8.global().set("recurse", recurse).unwrap();
+    let name: StdString = mv8.eval("try { recurse(); } catch (e) { e.name }").unwrap();
+    assert_eq!(name, "RangeError");
+}
+
+#[test]
+fn eval_sandboxed_code_generation() {
+    let mv8 = MiniV8::new();
+    let result = mv8.eval_sandboxed::<_, Value>("eval('1 + 1')", ResourceLimits::default());
+    match result {
+        Err(Error::Value(Value::Object(error))) => {
+            assert_eq!(error.get::<_, StdString>("name").unwrap(), "EvalError");
+        },
+        _ => panic!("unexpected result: {:?}", result),
+    }
+
+    let limits = ResourceLimits { allow_code_generation: true, ..Default::default() };
+    assert_eq!(mv8.eval_sandboxed::<_, usize>("eval('1 + 1')", limits).unwrap(), 2);
+    assert_eq!(mv8.eval::<_, usize>("new Function('return 3')()").unwrap(), 3);
+}
+
+#[test]
+fn eval_sandboxed_nested() {
+    let mv8 = MiniV8::new();
+    let func = mv8.create_function(|inv| {
+        inv.mv8.eval_sandboxed::<_, Value>("1", ResourceLimits::default())
+    });
+    mv8.global().set("nested", func).unwrap();
+    let message: StdString = mv8.eval("try { nested() } catch (e) { e.message }").unwrap();
+    assert_eq!(message, "invalid request for sandboxed evaluation");
+}
+
+#[test]
+fn parse_json() {
+    let mv8 = MiniV8::new();
+    let object: Object = mv8.parse_json(r#"{"a": [1, 2], "b": "c"}"#).unwrap().into(&mv8).unwrap();
+    assert_eq!(object.get::<_, Vec<usize>>("a").unwrap(), vec![1, 2]);
+    assert_eq!(object.get::<_, StdString>("b").unwrap(), "c");
+
+    match mv8.parse_json("{a: 1}") {
+        Err(Error::Value(Value::Object(error))) => {
+            assert_eq!(error.get::<_, StdString>("name").unwrap(), "SyntaxError");
+        },
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn indirect_eval() {
+    let mv8 = MiniV8::new();
+    let value = mv8.indirect_eval("var declared = 1; let scoped = 2; this === globalThis").unwrap();
+    assert_eq!(value.as_boolean(), Some(true));
+    assert_eq!(mv8.global().get::<_, u32>("declared").unwrap(), 1);
+    assert!(mv8.global().get::<_, Value>("scoped").unwrap().is_undefined());
+    assert_eq!(mv8.eval::<_, StdString>("typeof scoped").unwrap(), "undefined");
+    // Unlike a script's, the declared variable is deletable:
+    assert!(mv8.eval::<_, bool>("delete globalThis.declared").unwrap());
+
+    // Replacing the global `eval` does not affect it:
+    mv8.eval::<_, ()>("eval = () => 'replaced'").unwrap();
+    assert_eq!(mv8.indirect_eval("1 + 1").unwrap().as_number(), Some(2.0));
+    assert!(mv8.indirect_eval("throw new Error('oops')").is_err());
+}
+
+#[test]
+fn eval_checked() {
+    let mv8 = MiniV8::new();
+    assert_eq!(mv8.eval::<_, f64>("Infinity").unwrap(), f64::INFINITY);
+    assert_eq!(mv8.eval_checked::<_, u32>("40 + 2").unwrap(), 42);
+    assert_eq!(mv8.eval_checked::<_, f64>("1.5").unwrap(), 1.5);
+
+    for source in &["Infinity", "NaN", "1.5", "-1", "2 ** 32", "'abc'"] {
+        match mv8.eval_checked::<_, u32>(*source) {
+            Err(Error::FromJsConversionError { to: "u32", .. }) => {},
+            other => panic!("unexpected result for {}: {:?}", source, other),
+        }
+    }
+    assert!(mv8.eval_checked::<_, f64>("-Infinity").is_err());
+    assert_eq!(mv8.eval_checked::<_, i64>("-(2 ** 63)").unwrap(), i64::MIN);
+    assert!(mv8.eval_checked::<_, i64>("2 ** 63").is_err());
+}
+
+#[test]
+fn eval_finite() {
+    let mv8 = MiniV8::new();
+    assert_eq!(mv8.eval_finite("Math.sqrt(2) * Math.sqrt(2)").unwrap().round(), 2.0);
+    assert_eq!(mv8.eval_finite("'12.5'").unwrap(), 12.5);
+    for source in &["NaN", "0 / 0", "'abc'", "undefined", "1 / 0", "-Infinity"] {
+        match mv8.eval_finite(*source) {
+            Err(Error::FromJsConversionError { to: "f64", .. }) => {},
+            other => panic!("unexpected result for {}: {:?}", source, other),
+        }
+    }
+}
+
+#[test]
+fn check_syntax() {
+    let mv8 = MiniV8::new();
+    mv8.check_syntax("var a = 1; function f() { return a + 1; }").unwrap();
+    // The script is not run:
+    mv8.check_syntax("throw new Error('ran')").unwrap();
+    assert!(mv8.global().get::<_, Value>("a").unwrap().is_undefined());
+
+    match mv8.check_syntax("let = ;") {
+        Err(Error::Value(Value::Object(error))) => {
+            assert_eq!(error.get::<_, StdString>("name").unwrap(), "SyntaxError");
+        },
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn last_exception_details() {
+    let mv8 = MiniV8::new();
+    assert!(mv8.last_exception_details().is_none());
+    let result = mv8.eval::<_, Value>(Script {
+        source: "1 + 1;\nMISSING_VAR;".to_owned(),
+        origin: Some(ScriptOrigin {
+            name: "details".to_owned(),
+            line_offset: 10,
+            column_offset: 0,
+        }),
+        ..Default::default()
+    });
+    match result {
+        Err(Error::Value(_)) => {},
+        other => panic!("unexpected result: {:?}", other),
+    }
+    let details = mv8.last_exception_details().unwrap();
+    assert_eq!(details.message, "Uncaught ReferenceError: MISSING_VAR is not defined");
+    assert_eq!(details.resource_name.as_deref(), Some("details"));
+    assert_eq!(details.line_number, Some(12));
+    assert_eq!(details.start_column, 0);
+    assert_eq!(details.end_column, 11);
+    assert_eq!(details.source_line.as_deref(), Some("MISSING_VAR;"));
+    assert!(details.stack_trace.unwrap().contains("details:12:1"));
+
+    assert!(mv8.eval::<_, Value>("throw 1").is_err());
+    let details = mv8.last_exception_details().unwrap();
+    assert_eq!(details.message, "Uncaught 1");
+    assert_eq!(details.stack_trace, None);
+
+    // Exceptions caught and discarded internally, e.g. while formatting, are not recorded:
+    let func: Function = mv8.eval(r#"
+        var f = () => {};
+        Object.defineProperty(f, 'name', { get() { throw new Error('hidden'); } });
+        f
+    "#).unwrap();
+    assert_eq!(format!("{:?}", func), "<function <anonymous>(0)>");
+    assert_eq!(mv8.last_exception_details().unwrap().message, "Uncaught 1");
+}
+
+#[test]
+fn request_gc() {
+    // The tests run without V8's `--expose-gc` flag:
+    let mv8 = MiniV8::new();
+    mv8.eval::<_, ()>("globalThis.gc = () => {}").unwrap();
+    assert!(matches!(mv8.request_gc(GcKind::Full), Err(Error::GcUnavailable)));
+    assert!(matches!(mv8.request_gc(GcKind::Minor), Err(Error::GcUnavailable)));
+}
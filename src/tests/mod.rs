@@ -1,7 +1,21 @@
+mod allocator;
 mod array;
+mod array_buffer;
+mod bigint;
 mod conversion;
+mod error;
 mod function;
+mod map;
 mod mini_v8;
 mod object;
+mod profiler;
+mod promise;
+mod regexp;
+#[cfg(feature = "serde")] mod serde;
+mod set;
 mod string;
+mod symbol;
+mod typed_array;
 mod value;
+mod weak_map;
+mod weak_set;
@@ -1,4 +1,6 @@
 use crate::*;
+use std::cell::Cell;
+use std::rc::Rc;
 use std::string::String as StdString;
 
 #[test]
@@ -18,6 +20,27 @@ fn set_get() {
 }
 
 
+#[test]
+fn get_or_insert_with() {
+    let mv8 = MiniV8::new();
+    let object = mv8.create_object();
+    object.set("a", 1).unwrap();
+
+    let mut calls = 0;
+    let a: usize = object.get_or_insert_with("a", || { calls += 1; 100usize }).unwrap();
+    assert_eq!(a, 1);
+    assert_eq!(calls, 0);
+
+    let b: usize = object.get_or_insert_with("b", || { calls += 1; 2usize }).unwrap();
+    assert_eq!(b, 2);
+    assert_eq!(calls, 1);
+    assert_eq!(object.get::<_, usize>("b").unwrap(), 2);
+
+    let b: usize = object.get_or_insert_with("b", || { calls += 1; 200usize }).unwrap();
+    assert_eq!(b, 2);
+    assert_eq!(calls, 1);
+}
+
 #[test]
 fn remove() {
     let mv8 = MiniV8::new();
@@ -30,6 +53,199 @@ fn remove() {
     assert!(!globals.has("Object").unwrap());
 }
 
+#[test]
+fn clear() {
+    let mv8 = MiniV8::new();
+    let object: Object = mv8.eval("({ a: 1, b: 2, 3: 'c' })").unwrap();
+    object.clear().unwrap();
+    assert_eq!(object.keys(false).unwrap().len(), 0);
+    assert!(!object.has("a").unwrap());
+
+    // Non-configurable properties remain:
+    let pinned: Object = mv8
+        .eval("Object.defineProperty({ a: 1 }, 'b', { value: 2, enumerable: true })")
+        .unwrap();
+    pinned.clear().unwrap();
+    assert_eq!(pinned.keys(false).unwrap().len(), 1);
+    assert_eq!(pinned.get::<_, i32>("b").unwrap(), 2);
+}
+
+#[test]
+fn define_accessor() {
+    let mv8 = MiniV8::new();
+    mv8.set_user_data("counter", Cell::new(0));
+    let getter = mv8.create_function(|inv| {
+        Ok(inv.mv8.use_user_data("counter", |counter: Option<&Cell<i32>>| {
+            let counter = counter.unwrap();
+            counter.set(counter.get() + 1);
+            counter.get()
+        }))
+    });
+    let setter = mv8.create_function(|inv| {
+        let (value,): (i32,) = inv.args.into(&inv.mv8)?;
+        inv.mv8.use_user_data("counter", move |counter: Option<&Cell<i32>>| {
+            counter.unwrap().set(value);
+        });
+        Ok(())
+    });
+
+    let object = mv8.create_object();
+    object.define_getter("counter", getter.clone()).unwrap();
+    mv8.global().set("obj", object.clone()).unwrap();
+    assert_eq!(mv8.eval::<_, i32>("obj.counter + obj.counter").unwrap(), 3);
+    // Without a setter, assignments are ignored:
+    assert_eq!(mv8.eval::<_, i32>("obj.counter = 100; obj.counter").unwrap(), 3);
+
+    object.define_accessor("counter", Some(getter), Some(setter)).unwrap();
+    assert_eq!(mv8.eval::<_, i32>("obj.counter = 100; obj.counter").unwrap(), 101);
+    assert_eq!(object.keys(false).unwrap().len(), 1);
+}
+
+#[test]
+fn define_accessor_property() {
+    let mv8 = MiniV8::new();
+    let getter = mv8.create_function(|_| Ok(42));
+    let object = mv8.create_object();
+    object.set("visible", 1).unwrap();
+    object.define_accessor_property("answer", AccessorDescriptor {
+        getter: Some(getter),
+        setter: None,
+        enumerable: false,
+        configurable: false,
+    }).unwrap();
+    mv8.global().set("obj", object.clone()).unwrap();
+
+    assert_eq!(mv8.eval::<_, i32>("obj.answer").unwrap(), 42);
+    let keys: Vec<StdString> = object.keys(false).unwrap().elements().collect::<Result<_>>()
+        .unwrap();
+    assert_eq!(keys, vec!["visible"]);
+    match mv8.eval::<_, Value>("'use strict'; obj.answer = 1;") {
+        Err(Error::Value(Value::Object(error))) => {
+            assert_eq!(error.get::<_, StdString>("name").unwrap(), "TypeError");
+        },
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert!(!mv8.eval::<_, bool>("delete obj.answer").unwrap());
+
+    // Non-configurable properties can't be redefined:
+    let result = object.define_accessor_property("answer", AccessorDescriptor {
+        getter: None,
+        setter: None,
+        enumerable: true,
+        configurable: true,
+    });
+    match result {
+        Err(Error::Value(Value::Object(error))) => {
+            assert_eq!(error.get::<_, StdString>("name").unwrap(), "TypeError");
+        },
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn define_property() {
+    let mv8 = MiniV8::new();
+    let object = mv8.create_object();
+    object.set("visible", 1).unwrap();
+    object.define_property("hidden", PropertyDescriptor {
+        value: Value::Number(2.0),
+        writable: false,
+        enumerable: false,
+        configurable: true,
+    }).unwrap();
+
+    let keys: Vec<StdString> = object.keys(false).unwrap().elements().collect::<Result<_>>()
+        .unwrap();
+    assert_eq!(keys, vec!["visible"]);
+    assert_eq!(object.get::<_, i32>("hidden").unwrap(), 2);
+    object.set("hidden", 3).unwrap();
+    assert_eq!(object.get::<_, i32>("hidden").unwrap(), 2);
+
+    let descriptor = object.get_own_property_descriptor("hidden").unwrap().unwrap();
+    assert_eq!(descriptor.value.as_number(), Some(2.0));
+    assert!(!descriptor.writable);
+    assert!(!descriptor.enumerable);
+    assert!(descriptor.configurable);
+    let descriptor = object.get_own_property_descriptor("visible").unwrap().unwrap();
+    assert!(descriptor.writable && descriptor.enumerable && descriptor.configurable);
+    assert!(object.get_own_property_descriptor("missing").unwrap().is_none());
+
+    // Frozen objects don't allow new properties:
+    let frozen: Object = mv8.eval("Object.freeze({})").unwrap();
+    let result = frozen.define_property("value", PropertyDescriptor {
+        value: Value::Number(1.0),
+        writable: true,
+        enumerable: true,
+        configurable: true,
+    });
+    match result {
+        Err(Error::Value(Value::Object(error))) => {
+            assert_eq!(error.get::<_, StdString>("name").unwrap(), "TypeError");
+        },
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert!(!frozen.has("value").unwrap());
+}
+
+#[test]
+fn prototype() {
+    let mv8 = MiniV8::new();
+    let base: Object = mv8.eval("({ greet() { return 'hello ' + this.name; } })").unwrap();
+    let object = mv8.create_object_with_prototype(Value::Object(base.clone())).unwrap();
+    object.set("name", "world").unwrap();
+    assert_eq!(object.call_prop::<_, _, StdString>("greet", ()).unwrap(), "hello world");
+    match object.get_prototype().unwrap() {
+        Value::Object(prototype) => prototype.set("checked", true).unwrap(),
+        other => panic!("unexpected prototype: {:?}", other),
+    }
+    assert!(base.get::<_, bool>("checked").unwrap());
+
+    object.set_prototype(Value::Null).unwrap();
+    assert!(object.get_prototype().unwrap().is_null());
+    assert!(!object.has("greet").unwrap());
+    let bare = mv8.create_object_with_prototype(Value::Null).unwrap();
+    assert!(!bare.has("toString").unwrap());
+
+    match object.set_prototype(Value::Number(1.0)) {
+        Err(Error::Value(Value::Object(error))) => {
+            assert_eq!(error.get::<_, StdString>("name").unwrap(), "TypeError");
+        },
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn freeze_seal() {
+    let mv8 = MiniV8::new();
+    let frozen = mv8.create_object();
+    frozen.set("a", 1).unwrap();
+    assert!(!frozen.is_frozen().unwrap());
+    frozen.freeze().unwrap();
+    assert!(frozen.is_frozen().unwrap());
+    assert!(frozen.is_sealed().unwrap());
+    frozen.set("a", 2).unwrap();
+    frozen.set("b", 3).unwrap();
+    assert_eq!(frozen.get::<_, i32>("a").unwrap(), 1);
+    assert!(!frozen.has("b").unwrap());
+
+    mv8.global().set("frozen", frozen).unwrap();
+    match mv8.eval::<_, Value>("'use strict'; frozen.a = 2") {
+        Err(Error::Value(Value::Object(error))) => {
+            assert_eq!(error.get::<_, StdString>("name").unwrap(), "TypeError");
+        },
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    let sealed = mv8.create_object();
+    sealed.set("a", 1).unwrap();
+    sealed.seal().unwrap();
+    assert!(sealed.is_sealed().unwrap());
+    assert!(!sealed.is_frozen().unwrap());
+    sealed.set("a", 2).unwrap();
+    sealed.remove("a").unwrap();
+    assert_eq!(sealed.get::<_, i32>("a").unwrap(), 2);
+}
+
 #[test]
 fn has() {
     let mv8 = MiniV8::new();
@@ -65,3 +281,289 @@ fn properties() {
 
     assert_eq!(list, vec![("4".to_string(), 0), ("123".to_string(), 456), ("a".to_string(), 123)]);
 }
+
+#[test]
+fn iter() {
+    let mv8 = MiniV8::new();
+    let object: Object = mv8.eval("({ a: 1, b: 2 })").unwrap();
+    for _ in 0..2 {
+        let properties = object.iter::<StdString, u32>(false).unwrap()
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(properties, vec![("a".to_owned(), 1), ("b".to_owned(), 2)]);
+    }
+    object.set("c", 3).unwrap();
+    assert_eq!(object.iter::<Value, Value>(false).unwrap().count(), 3);
+}
+
+#[test]
+fn define_method_weak() {
+    struct DropFlag(Rc<Cell<bool>>);
+
+    impl Drop for DropFlag {
+        fn drop(&mut self) {
+            self.0.set(true);
+        }
+    }
+
+    let mv8 = MiniV8::new();
+    let dropped = Rc::new(Cell::new(false));
+    let flag = DropFlag(dropped.clone());
+
+    let object = mv8.create_object();
+    object.set("value", 5).unwrap();
+    object.define_method_weak("double", move |_, this, _| {
+        let _ = &flag;
+        let value: f64 = this.get("value")?;
+        Ok(value * 2.0)
+    }).unwrap();
+    let value: f64 = object.call_prop("double", ()).unwrap();
+    assert_eq!(value, 10.0);
+
+    // The method does not hold the object, so dropping our handle lets both be collected:
+    drop(object);
+    mv8.low_memory_notification();
+    assert!(dropped.get());
+}
+
+#[test]
+fn estimated_size() {
+    let mv8 = MiniV8::new();
+    let object: Object = mv8.eval("({ a: 1, b: { c: 'two', d: [3, 4] } })").unwrap();
+    assert_eq!(object.estimated_size().unwrap(), 7);
+
+    let object: Object = mv8
+        .eval("let o = { x: 1, y: {} }; o.y.parent = o; o.self = o; o")
+        .unwrap();
+    assert_eq!(object.estimated_size().unwrap(), 3);
+
+    let object: Object = mv8.eval("let shared = [1]; ({ a: shared, b: shared })").unwrap();
+    assert_eq!(object.estimated_size().unwrap(), 3);
+}
+
+#[test]
+fn debug_frozen() {
+    let mv8 = MiniV8::new();
+    let object: Object = mv8.eval("({ a: 1 })").unwrap();
+    assert_eq!(format!("{:?}", object), r#"{ "a": 1 }"#);
+    object.freeze().unwrap();
+    assert_eq!(format!("{:?}", object), r#"[frozen] { "a": 1 }"#);
+    let empty: Object = mv8.eval("Object.freeze({})").unwrap();
+    assert_eq!(format!("{:?}", empty), "[frozen] {}");
+}
+
+#[test]
+fn debug_depth_and_proxies() {
+    let mv8 = MiniV8::new();
+    let cyclic: Object = mv8.eval("var c = { a: [1] }; c.a.push(c); c").unwrap();
+    let debug = format!("{:?}", cyclic);
+    assert!(debug.starts_with(r#"{ "a": [1, { "a": [1, { "a": [1, { "a": [1, {...}] }"#));
+
+    let proxy: Object = mv8.eval(r#"
+        var traps = 0;
+        new Proxy({ a: 1 }, { ownKeys() { traps++; return []; } })
+    "#).unwrap();
+    assert_eq!(format!("{:?}", proxy), "<proxy>");
+    assert_eq!(mv8.eval::<_, u32>("traps").unwrap(), 0);
+}
+
+#[test]
+fn ptr_eq() {
+    let mv8 = MiniV8::new();
+    let object = mv8.create_object();
+    mv8.global().set("object", object.clone()).unwrap();
+    let same: Object = mv8.eval("object").unwrap();
+    let similar: Object = mv8.eval("({})").unwrap();
+    assert!(object.ptr_eq(&same));
+    assert!(!object.ptr_eq(&similar));
+
+    let array: Array = mv8.eval("globalThis.array = [1, 2]").unwrap();
+    assert!(array.ptr_eq(&mv8.global().get("array").unwrap()));
+    assert!(!array.ptr_eq(&mv8.eval("[1, 2]").unwrap()));
+
+    let function: Function = mv8.eval("globalThis.f = () => {}").unwrap();
+    assert!(function.ptr_eq(&mv8.global().get("f").unwrap()));
+    assert!(!function.ptr_eq(&mv8.eval("() => {}").unwrap()));
+
+    let mut handles = vec![object.clone(), same, object, similar];
+    handles.dedup_by(|a, b| a.ptr_eq(b));
+    assert_eq!(handles.len(), 2);
+}
+
+#[test]
+fn partial_eq() {
+    let mv8 = MiniV8::new();
+    let a: Object = mv8.eval("({ x: 1, y: [2, { z: 3 }] })").unwrap();
+    let b: Object = mv8.eval("({ y: [2, { z: 3 }], x: 1 })").unwrap();
+    let c: Object = mv8.eval("({ x: 1, y: [2, { z: 4 }] })").unwrap();
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+    assert_eq!(a, a.clone());
+
+    // Errors thrown by getters make objects unequal without being recorded:
+    let d: Object = mv8.eval("({ get x() { throw new Error('getter') } })").unwrap();
+    let e: Object = mv8.eval("({ x: 1 })").unwrap();
+    assert!(mv8.eval::<_, Value>("throw 1").is_err());
+    assert_ne!(d, e);
+    assert_eq!(mv8.last_exception_details().unwrap().message, "Uncaught 1");
+}
+
+#[test]
+fn call_prop_chained() {
+    let mv8 = MiniV8::new();
+    let array: Object = mv8.eval("[]").unwrap();
+    let result = array.call_prop_chained("push", (1, 2)).unwrap()
+        .call_prop_chained("push", (3,)).unwrap()
+        .call_prop_chained("reverse", ()).unwrap();
+    assert_eq!(result, array);
+    assert_eq!(array.call_prop::<_, _, StdString>("join", ()).unwrap(), "3,2,1");
+
+    let object = mv8.create_object();
+    match object.call_prop_chained("missing", ()) {
+        Err(Error::FromJsConversionError { .. }) => {},
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn internal_fields() {
+    struct DropFlag(Rc<Cell<bool>>);
+
+    impl Drop for DropFlag {
+        fn drop(&mut self) {
+            self.0.set(true);
+        }
+    }
+
+    let mv8 = MiniV8::new();
+    let object = mv8.create_object_with_internal_fields(2);
+    assert_eq!(object.internal_field_count(), 2);
+    assert_eq!(mv8.create_object().internal_field_count(), 0);
+    assert!(object.get_internal_field::<StdString>(0).unwrap().is_none());
+
+    object.set_internal_field(0, "handle".to_string()).unwrap();
+    assert_eq!(*object.get_internal_field::<StdString>(0).unwrap().unwrap(), "handle");
+    assert!(object.get_internal_field::<i32>(0).unwrap().is_none());
+    assert!(object.get_internal_field::<StdString>(1).unwrap().is_none());
+    match object.set_internal_field(2, 0) {
+        Err(Error::OutOfBounds { index: 2, len: 2 }) => {},
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    // Replacing data drops the previous data:
+    let replaced = Rc::new(Cell::new(false));
+    object.set_internal_field(1, DropFlag(replaced.clone())).unwrap();
+    object.set_internal_field(1, 42).unwrap();
+    assert!(replaced.get());
+    assert_eq!(*object.get_internal_field::<i32>(1).unwrap().unwrap(), 42);
+
+    // Internal fields are invisible to JavaScript:
+    assert_eq!(object.keys(false).unwrap().len(), 0);
+
+    let dropped = Rc::new(Cell::new(false));
+    object.set_internal_field(0, DropFlag(dropped.clone())).unwrap();
+    drop(object);
+    mv8.low_memory_notification();
+    assert!(dropped.get());
+
+    // Data returned by `get_internal_field` outlives the object:
+    let object = mv8.create_object_with_internal_fields(1);
+    object.set_internal_field(0, "kept".to_string()).unwrap();
+    let kept = object.get_internal_field::<StdString>(0).unwrap().unwrap();
+    drop(object);
+    mv8.low_memory_notification();
+    assert_eq!(*kept, "kept");
+}
+
+#[test]
+fn len() {
+    let mv8 = MiniV8::new();
+    let object = mv8.create_object();
+    assert_eq!(object.len().unwrap(), 0);
+    assert!(object.is_empty().unwrap());
+
+    object.set("a", 1).unwrap();
+    object.set(2, 2).unwrap();
+    assert_eq!(object.len().unwrap(), 2);
+    assert!(!object.is_empty().unwrap());
+
+    // Inherited and non-enumerable properties are excluded, as with `Object::keys(false)`:
+    let object: Object = mv8.eval(r#"
+        const object = Object.create({ inherited: true });
+        Object.defineProperty(object, 'hidden', { value: 1, enumerable: false });
+        object.own = 1;
+        object
+    "#).unwrap();
+    assert_eq!(object.len().unwrap(), 1);
+    assert_eq!(object.len().unwrap(), object.keys(false).unwrap().len());
+}
+
+#[test]
+fn entries_and_values() {
+    let mv8 = MiniV8::new();
+    let object: Object = mv8.eval("Object.assign(Object.create({ c: 3 }), { a: 1, b: 2 })")
+        .unwrap();
+
+    let entries: Vec<(StdString, u32)> = object.entries(false).unwrap();
+    assert_eq!(entries, vec![("a".to_string(), 1), ("b".to_string(), 2)]);
+    let values: Vec<u32> = object.values(false).unwrap();
+    assert_eq!(values, vec![1, 2]);
+    let values: Vec<u32> = object.values(true).unwrap();
+    assert_eq!(values, vec![1, 2, 3]);
+
+    object.set("d", "four").unwrap();
+    match object.values::<u32>(false) {
+        Err(Error::FromJsConversionError { from: "string", to: "u32" }) => {},
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert!(object.entries::<StdString, u32>(false).is_err());
+}
+
+#[test]
+fn assign() {
+    let mv8 = MiniV8::new();
+    let target: Object = mv8.eval("({ a: 1, b: 1 })").unwrap();
+    let source: Object = mv8.eval(r#"
+        Object.defineProperty({ b: 2, get c() { return this.b + 1; } }, 'hidden', { value: 0 })
+    "#).unwrap();
+    target.assign(&source).unwrap();
+    assert_eq!(target.get::<_, u32>("a").unwrap(), 1);
+    assert_eq!(target.get::<_, u32>("b").unwrap(), 2);
+    // The getter was invoked, so `c` is copied as a data property:
+    assert_eq!(target.get::<_, u32>("c").unwrap(), 3);
+    assert!(!target.has("hidden").unwrap());
+
+    let throwing: Object = mv8.eval("({ get bad() { throw new Error('oops'); } })").unwrap();
+    match target.assign(&throwing) {
+        Err(Error::Value(Value::Object(error))) => {
+            assert_eq!(error.get::<_, StdString>("message").unwrap(), "oops");
+        },
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    let defaults: Object = mv8.eval("({ port: 80, host: 'localhost' })").unwrap();
+    let overrides: Object = mv8.eval("({ port: 8080 })").unwrap();
+    let merged = mv8.create_object_merged(&[defaults.clone(), overrides]).unwrap();
+    assert_eq!(merged.get::<_, u32>("port").unwrap(), 8080);
+    assert_eq!(merged.get::<_, StdString>("host").unwrap(), "localhost");
+    assert_eq!(defaults.get::<_, u32>("port").unwrap(), 80);
+    assert_eq!(mv8.create_object_merged(&[]).unwrap().len().unwrap(), 0);
+}
+
+#[test]
+fn key_at() {
+    let mv8 = MiniV8::new();
+    let object = mv8.create_object_from((0..100).map(|i| (format!("key{}", i), i))).unwrap();
+
+    let mut keys = Vec::new();
+    for page in 0..10 {
+        for index in page * 10..(page + 1) * 10 {
+            let key = object.key_at(index).unwrap().unwrap();
+            keys.push(key.coerce_string(&mv8).unwrap().to_string());
+        }
+    }
+    let expected: Vec<StdString> = (0..100).map(|i| format!("key{}", i)).collect();
+    assert_eq!(keys, expected);
+    assert!(object.key_at(100).unwrap().is_none());
+    assert!(mv8.create_object().key_at(0).unwrap().is_none());
+}
@@ -0,0 +1,33 @@
+use crate::*;
+
+#[test]
+fn cpu_profile() {
+    let mv8 = MiniV8::new();
+    assert!(matches!(mv8.stop_cpu_profile(), Err(Error::NoCpuProfile)));
+
+    mv8.start_cpu_profile("discarded").unwrap();
+    mv8.start_cpu_profile("loop").unwrap();
+    let _: f64 = mv8.eval(Script {
+        source: r#"
+            function spin() {
+                let sum = 0;
+                for (let i = 0; i < 5e6; i++) { sum += Math.sqrt(i); }
+                return sum;
+            }
+            spin() + spin()
+        "#.to_owned(),
+        origin: Some(ScriptOrigin { name: "spin.js".to_owned(), ..Default::default() }),
+        ..Default::default()
+    }).unwrap();
+    let profile = mv8.stop_cpu_profile().unwrap();
+
+    assert_eq!(profile.name, "loop");
+    assert!(profile.sample_count > 0);
+    assert_eq!(profile.sample_count, profile.functions.iter().map(|f| f.sample_count).sum());
+    let spin = profile.functions.iter().find(|f| f.name == "spin").unwrap();
+    assert_eq!(spin.url, "spin.js");
+    assert_eq!(spin.line_number, Some(1));
+    assert!(spin.total_time >= spin.self_time);
+    assert!(profile.json.starts_with('{'));
+    assert!(matches!(mv8.stop_cpu_profile(), Err(Error::NoCpuProfile)));
+}
@@ -0,0 +1,63 @@
+use crate::*;
+use std::string::String as StdString;
+
+#[test]
+fn fulfill() {
+    let mv8 = MiniV8::new();
+    let promise: Promise = mv8.eval(r#"
+        var resolvePromise;
+        new Promise(resolve => { resolvePromise = resolve; }).then(x => x * 2)
+    "#).unwrap();
+    assert_eq!(promise.state(), PromiseState::Pending);
+    assert!(promise.result().is_none());
+
+    let resolve: Function = mv8.global().get("resolvePromise").unwrap();
+    resolve.call::<_, ()>((21,)).unwrap();
+    mv8.run_microtasks();
+    assert_eq!(promise.state(), PromiseState::Fulfilled);
+    assert_eq!(promise.result().unwrap().into::<usize>(&mv8).unwrap(), 42);
+}
+
+#[test]
+fn reject() {
+    let mv8 = MiniV8::new();
+    let value: Value = mv8.eval("(async () => { throw 'oops'; })()").unwrap();
+    assert!(value.is_promise());
+    mv8.run_microtasks();
+    let promise = value.as_promise().unwrap();
+    assert_eq!(promise.state(), PromiseState::Rejected);
+    let reason: StdString = promise.result().unwrap().into(&mv8).unwrap();
+    assert_eq!(reason, "oops");
+}
+
+#[test]
+fn async_function() {
+    let mv8 = MiniV8::new();
+    let sqrt = mv8.create_async_function(|inv| {
+        let (x,): (f64,) = inv.args.into(&inv.mv8)?;
+        if x < 0.0 {
+            return Err(Error::ExternalError("negative input".into()));
+        }
+        Ok(x.sqrt())
+    });
+    mv8.global().set("sqrt", sqrt.clone()).unwrap();
+
+    let fulfilled: Promise = sqrt.call((16,)).unwrap();
+    assert_eq!(fulfilled.state(), PromiseState::Fulfilled);
+    assert_eq!(fulfilled.result().unwrap().as_number(), Some(4.0));
+
+    let promise: Promise = mv8.eval(r#"
+        (async () => {
+            const root = await sqrt(9);
+            try {
+                await sqrt(-1);
+            } catch (e) {
+                return `${root} ${e.message}`;
+            }
+        })()
+    "#).unwrap();
+    mv8.run_microtasks();
+    assert_eq!(promise.state(), PromiseState::Fulfilled);
+    let result: StdString = promise.result().unwrap().into(&mv8).unwrap();
+    assert_eq!(result, "3 negative input");
+}
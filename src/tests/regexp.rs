@@ -0,0 +1,36 @@
+use crate::*;
+use std::string::String as StdString;
+
+#[test]
+fn create() {
+    let mv8 = MiniV8::new();
+    let flags = RegExpFlags { ignore_case: true, ..Default::default() };
+    let regexp = mv8.create_regexp("^a+b$", flags).unwrap();
+    assert_eq!(regexp.source(), "^a+b$");
+    assert_eq!(regexp.flags().unwrap(), flags);
+    assert!(regexp.test("AAb").unwrap());
+    assert!(!regexp.test("abc").unwrap());
+    assert_eq!(format!("{:?}", regexp), "/^a+b$/i");
+
+    match mv8.create_regexp("(", RegExpFlags::default()) {
+        Err(Error::Value(Value::Object(error))) => {
+            assert_eq!(error.get::<_, StdString>("name").unwrap(), "SyntaxError");
+        },
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn from_script() {
+    let mv8 = MiniV8::new();
+    let value: Value = mv8.eval(r"/(\d+)-(\d+)/gu").unwrap();
+    assert!(value.is_regexp());
+    let regexp = value.as_regexp().unwrap();
+    let flags = regexp.flags().unwrap();
+    assert!(flags.global && flags.unicode && !flags.sticky);
+
+    let groups: Vec<StdString> = regexp.exec("range 10-20").unwrap().unwrap().elements()
+        .collect::<Result<_>>().unwrap();
+    assert_eq!(groups, vec!["10-20", "10", "20"]);
+    assert!(regexp.exec("no match").unwrap().is_none());
+}
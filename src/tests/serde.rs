@@ -0,0 +1,165 @@
+use crate::*;
+use ::serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::string::String as StdString;
+
+#[derive(Serialize)]
+struct Config {
+    name: StdString,
+    retries: u32,
+    ratio: f64,
+    tags: Vec<StdString>,
+    parent: Option<Box<Config>>,
+    limits: BTreeMap<StdString, u64>,
+    mode: Mode,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+enum Mode {
+    Fast,
+    Custom(u8),
+    Pair(bool, bool),
+    Named { level: i8 },
+}
+
+#[test]
+fn serialize_struct() {
+    let mv8 = MiniV8::new();
+    let mut limits = BTreeMap::new();
+    limits.insert("memory".to_owned(), 1024);
+    let config = Config {
+        name: "child".to_owned(),
+        retries: 3,
+        ratio: 0.5,
+        tags: vec!["a".to_owned(), "b".to_owned()],
+        parent: Some(Box::new(Config {
+            name: "parent".to_owned(),
+            retries: 0,
+            ratio: 1.0,
+            tags: vec![],
+            parent: None,
+            limits: BTreeMap::new(),
+            mode: Mode::Fast,
+        })),
+        limits,
+        mode: Mode::Named { level: -1 },
+    };
+
+    let value = serde::to_value(&mv8, &config).unwrap();
+    let json = value.to_json(&mv8).unwrap().to_string();
+    assert_eq!(json, concat!(
+        r#"{"name":"child","retries":3,"ratio":0.5,"tags":["a","b"],"#,
+        r#""parent":{"name":"parent","retries":0,"ratio":1,"tags":[],"parent":null,"limits":{},"#,
+        r#""mode":"Fast"},"limits":{"memory":1024},"mode":{"Named":{"level":-1}}}"#,
+    ));
+}
+
+#[test]
+fn serialize_enum() {
+    let mv8 = MiniV8::new();
+    let to_json = |mode: &Mode| {
+        serde::to_value(&mv8, mode).unwrap().to_json(&mv8).unwrap().to_string()
+    };
+    assert_eq!(to_json(&Mode::Fast), r#""Fast""#);
+    assert_eq!(to_json(&Mode::Custom(7)), r#"{"Custom":7}"#);
+    assert_eq!(to_json(&Mode::Pair(true, false)), r#"{"Pair":[true,false]}"#);
+}
+
+#[test]
+fn serialize_primitives() {
+    let mv8 = MiniV8::new();
+    assert!(serde::to_value(&mv8, &None::<u8>).unwrap().is_null());
+    assert_eq!(serde::to_value(&mv8, &1.5f32).unwrap().as_number(), Some(1.5));
+    assert!(serde::to_value(&mv8, &(1u128 << 100)).unwrap().is_bigint());
+    let tuple: Array = serde::to_value(&mv8, &(1, "two")).unwrap().into(&mv8).unwrap();
+    assert_eq!(tuple.get::<StdString>(1).unwrap(), "two");
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Settings {
+    name: StdString,
+    port: u16,
+    ratio: f64,
+    tags: Vec<StdString>,
+    timeout: Option<u32>,
+    limits: BTreeMap<StdString, i64>,
+    kinds: Vec<Kind>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+enum Kind {
+    Plain,
+    Sized(u8),
+    Range { start: i32, end: i32 },
+}
+
+#[test]
+fn deserialize_struct() {
+    let mv8 = MiniV8::new();
+    let value: Value = mv8.eval(r#"({
+        name: "server",
+        port: 8080,
+        ratio: 0.25,
+        tags: ["a", "b"],
+        limits: { memory: -1 },
+        kinds: ["Plain", { Sized: 4 }, { Range: { start: 1, end: 2 } }],
+    })"#).unwrap();
+    let settings: Settings = serde::from_value(value, &mv8).unwrap();
+    let mut limits = BTreeMap::new();
+    limits.insert("memory".to_owned(), -1);
+    assert_eq!(settings, Settings {
+        name: "server".to_owned(),
+        port: 8080,
+        ratio: 0.25,
+        tags: vec!["a".to_owned(), "b".to_owned()],
+        timeout: None,
+        limits,
+        kinds: vec![Kind::Plain, Kind::Sized(4), Kind::Range { start: 1, end: 2 }],
+    });
+}
+
+#[test]
+fn deserialize_strict_numbers() {
+    let mv8 = MiniV8::new();
+    let from_js = |source: &str| -> Result<u32> {
+        serde::from_value(mv8.eval(source).unwrap(), &mv8)
+    };
+    assert_eq!(from_js("4294967295").unwrap(), u32::MAX);
+    assert!(from_js("1.5").is_err());
+    assert!(from_js("-1").is_err());
+    assert!(from_js("4294967296").is_err());
+    assert!(from_js("'123'").is_err());
+    assert!(from_js("NaN").is_err());
+}
+
+#[test]
+fn round_trip() {
+    let mv8 = MiniV8::new();
+    let modes = vec![
+        Mode::Fast,
+        Mode::Custom(7),
+        Mode::Pair(true, false),
+        Mode::Named { level: -1 },
+    ];
+    let value = serde::to_value(&mv8, &modes).unwrap();
+    let result: Vec<Mode> = serde::from_value(value, &mv8).unwrap();
+    assert_eq!(result, modes);
+}
+
+#[test]
+fn round_trip_64_bit_integers() {
+    let mv8 = MiniV8::new();
+    assert!(serde::to_value(&mv8, &(1u64 << 53)).unwrap().is_bigint());
+    assert!(serde::to_value(&mv8, &((1u64 << 53) - 1)).unwrap().is_number());
+    assert!(serde::to_value(&mv8, &i64::MIN).unwrap().is_bigint());
+
+    let value = serde::to_value(&mv8, &u64::MAX).unwrap();
+    assert_eq!(serde::from_value::<u64>(value, &mv8).unwrap(), u64::MAX);
+    let value = serde::to_value(&mv8, &i64::MIN).unwrap();
+    assert_eq!(serde::from_value::<i64>(value, &mv8).unwrap(), i64::MIN);
+    let value = serde::to_value(&mv8, &vec![u64::MAX, 1]).unwrap();
+    assert_eq!(serde::from_value::<Vec<u64>>(value, &mv8).unwrap(), vec![u64::MAX, 1]);
+
+    assert!(serde::from_value::<u64>(mv8.eval("2n ** 64n").unwrap(), &mv8).is_err());
+    assert!(serde::from_value::<i64>(mv8.eval("2n ** 63n").unwrap(), &mv8).is_err());
+}
@@ -0,0 +1,37 @@
+use crate::*;
+
+#[test]
+fn add_has_remove() {
+    let mv8 = MiniV8::new();
+    let set = mv8.create_set();
+    set.add(1).unwrap();
+    set.add("a").unwrap();
+    set.add(1).unwrap();
+    assert_eq!(set.len(), 2);
+    assert!(set.has(1).unwrap());
+    assert!(set.has("a").unwrap());
+    assert!(!set.has("1").unwrap());
+    assert!(set.remove(1).unwrap());
+    assert!(!set.remove(1).unwrap());
+    assert_eq!(set.len(), 1);
+}
+
+#[test]
+fn create_set_from() {
+    let mv8 = MiniV8::new();
+    let set = mv8.create_set_from(vec![3, 1, 3, 2]).unwrap();
+    let values: Vec<usize> = set.values().collect::<Result<_>>().unwrap();
+    assert_eq!(values, vec![3, 1, 2]);
+}
+
+#[test]
+fn from_script() {
+    let mv8 = MiniV8::new();
+    let value: Value = mv8.eval("new Set(['x', 'y'])").unwrap();
+    assert!(value.is_set());
+    assert_eq!(format!("{:?}", value), r#"Set { "x", "y" }"#);
+    let values: Vec<Value> = value.as_set().unwrap().clone().values().collect::<Result<_>>()
+        .unwrap();
+    assert_eq!(values.len(), 2);
+    assert_eq!(values[1].as_string().unwrap().to_string(), "y");
+}
@@ -1,7 +1,32 @@
 use crate::mini_v8::MiniV8;
+use std::rc::Rc;
 
 #[test]
 fn to_string() {
     let mv8 = MiniV8::new();
     assert_eq!(mv8.create_string("abc😊🈹").to_string(), "abc😊🈹".to_string());
 }
+
+#[test]
+fn as_cached_str() {
+    let mv8 = MiniV8::new();
+    let string = mv8.create_string("key");
+    let first = string.as_cached_str();
+    let second = string.as_cached_str();
+    assert_eq!(&*first, "key");
+    assert!(Rc::ptr_eq(&first, &second));
+    assert!(Rc::ptr_eq(&first, &string.clone().as_cached_str()));
+}
+
+#[test]
+fn create_internalized_string() {
+    let mv8 = MiniV8::new();
+    let key = mv8.create_internalized_string("key😊");
+    assert_eq!(key.to_string(), "key😊");
+
+    let object = mv8.create_object();
+    object.set(key, 1).unwrap();
+    assert_eq!(object.get::<_, i32>(mv8.create_internalized_string("key😊")).unwrap(), 1);
+    assert_eq!(object.get::<_, i32>(mv8.create_string("key😊")).unwrap(), 1);
+    assert_eq!(object.get::<_, i32>("key😊").unwrap(), 1);
+}
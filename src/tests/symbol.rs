@@ -0,0 +1,45 @@
+use crate::*;
+use std::string::String as StdString;
+
+#[test]
+fn from_script() {
+    let mv8 = MiniV8::new();
+    let value: Value = mv8.eval("Symbol.iterator").unwrap();
+    assert!(value.is_symbol());
+    assert_eq!(value.as_symbol().unwrap().description().unwrap(), "Symbol.iterator");
+    assert_eq!(format!("{:?}", value), "Symbol(Symbol.iterator)");
+}
+
+#[test]
+fn description() {
+    let mv8 = MiniV8::new();
+    assert_eq!(mv8.create_symbol(Some("abc")).description().unwrap(), "abc");
+    assert!(mv8.create_symbol(None).description().is_none());
+}
+
+#[test]
+fn registry() {
+    let mv8 = MiniV8::new();
+    let symbol = mv8.symbol_for("key");
+    let same: Function = mv8.eval("s => s === Symbol.for('key')").unwrap();
+    assert!(same.call::<_, bool>((symbol.clone(),)).unwrap());
+    assert!(!same.call::<_, bool>((mv8.create_symbol(Some("key")),)).unwrap());
+}
+
+#[test]
+fn object_key() {
+    let mv8 = MiniV8::new();
+    let object = mv8.create_object();
+    let symbol = mv8.create_symbol(Some("key"));
+    object.set(symbol.clone(), 123).unwrap();
+    assert!(object.has(symbol.clone()).unwrap());
+    assert!(!object.has("key").unwrap());
+    assert_eq!(object.get::<_, usize>(symbol.clone()).unwrap(), 123);
+    object.remove(symbol.clone()).unwrap();
+    assert!(!object.has(symbol).unwrap());
+
+    let global = mv8.global();
+    global.set("obj", object.clone()).unwrap();
+    let tag: StdString = mv8.eval("obj[Symbol.for('tag')] = 'x'").unwrap();
+    assert_eq!(object.get::<_, StdString>(mv8.symbol_for("tag")).unwrap(), tag);
+}
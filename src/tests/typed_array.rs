@@ -0,0 +1,48 @@
+use crate::*;
+
+#[test]
+fn uint8_array() {
+    let mv8 = MiniV8::new();
+    let buffer = mv8.create_array_buffer_from(vec![1, 2, 3, 4, 5]);
+    let array = mv8.create_uint8_array(&buffer, 1, 3).unwrap();
+    assert_eq!(array.kind(), TypedArrayKind::Uint8);
+    assert_eq!(array.len(), 3);
+    assert_eq!(array.copy_to_vec::<u8>().unwrap(), vec![2, 3, 4]);
+    assert_eq!(array.buffer().byte_length(), 5);
+
+    match mv8.create_uint8_array(&buffer, 3, 3) {
+        Err(Error::OutOfBounds { index: 6, len: 5 }) => {},
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    let sum: Function = mv8.eval("a => a.reduce((acc, x) => acc + x, 0)").unwrap();
+    let array = mv8.create_uint8_array_from(&[10, 20, 30]);
+    assert_eq!(sum.call::<_, usize>((array,)).unwrap(), 60);
+}
+
+#[test]
+fn from_script() {
+    let mv8 = MiniV8::new();
+    let value: Value = mv8.eval("new Float64Array([1.5, -2.25])").unwrap();
+    assert!(value.is_typed_array());
+    let array = value.as_typed_array().unwrap();
+    assert_eq!(array.kind(), TypedArrayKind::Float64);
+    assert_eq!(array.byte_length(), 16);
+    assert_eq!(array.copy_to_vec::<f64>().unwrap(), vec![1.5, -2.25]);
+
+    let array: TypedArray = mv8.eval("new Int16Array([-1, 300])").unwrap();
+    assert_eq!(array.copy_to_vec::<i16>().unwrap(), vec![-1, 300]);
+}
+
+#[test]
+fn kind_mismatch() {
+    let mv8 = MiniV8::new();
+    let array: TypedArray = mv8.eval("new Float64Array([1])").unwrap();
+    match array.copy_to_vec::<u8>() {
+        Err(Error::FromJsConversionError { from: "Float64Array", to: "u8" }) => {},
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    let clamped: TypedArray = mv8.eval("new Uint8ClampedArray([300])").unwrap();
+    assert_eq!(clamped.copy_to_vec::<u8>().unwrap(), vec![255]);
+}
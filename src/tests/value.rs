@@ -1,4 +1,5 @@
 use crate::*;
+use std::string::String as StdString;
 
 #[test]
 fn coerce_boolean() {
@@ -37,3 +38,250 @@ fn coerce_string() {
     assert_string_eq(&mv8, Value::String(mv8.create_string("abc")), "abc");
     assert_string_eq(&mv8, Value::Object(mv8.create_object()), "[object Object]");
 }
+
+#[test]
+fn kind() {
+    let mv8 = MiniV8::new();
+    assert_eq!(Value::Undefined.kind(), ValueKind::Undefined);
+    assert_eq!(Value::Null.kind(), ValueKind::Null);
+    assert_eq!(Value::Boolean(true).kind(), ValueKind::Boolean);
+    assert_eq!(Value::Number(1.0).kind(), ValueKind::Number);
+    assert_eq!(mv8.eval::<_, Value>("1n").unwrap().kind(), ValueKind::BigInt);
+    assert_eq!(Value::Date(0.0).kind(), ValueKind::Date);
+    assert_eq!(Value::String(mv8.create_string("a")).kind(), ValueKind::String);
+    assert_eq!(Value::Symbol(mv8.create_symbol(None)).kind(), ValueKind::Symbol);
+    assert_eq!(Value::Array(mv8.create_array()).kind(), ValueKind::Array);
+    assert_eq!(
+        Value::ArrayBuffer(mv8.create_array_buffer_from(vec![])).kind(),
+        ValueKind::ArrayBuffer,
+    );
+    assert_eq!(Value::Function(mv8.create_function(|_| Ok(()))).kind(), ValueKind::Function);
+    assert_eq!(mv8.eval::<_, Value>("Promise.resolve()").unwrap().kind(), ValueKind::Promise);
+    assert_eq!(Value::Object(mv8.create_object()).kind(), ValueKind::Object);
+}
+
+#[test]
+fn to_json() {
+    let mv8 = MiniV8::new();
+    let value: Value = mv8.eval("({ a: [1, 'two', null], b: { c: true } })").unwrap();
+    assert_eq!(value.to_json(&mv8).unwrap().to_string(), r#"{"a":[1,"two",null],"b":{"c":true}}"#);
+    assert_eq!(
+        value.to_json_pretty(&mv8, 2).unwrap().to_string(),
+        "{\n  \"a\": [\n    1,\n    \"two\",\n    null\n  ],\n  \"b\": {\n    \"c\": true\n  }\n}",
+    );
+}
+
+#[test]
+fn to_json_cycle() {
+    let mv8 = MiniV8::new();
+    let value: Value = mv8.eval("let a = {}; a.self = a; a").unwrap();
+    for result in [value.to_json(&mv8), value.to_json_pretty(&mv8, 2)] {
+        match result {
+            Err(Error::Value(Value::Object(error))) => {
+                assert_eq!(error.get::<_, StdString>("name").unwrap(), "TypeError");
+            },
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn instance_of() {
+    let mv8 = MiniV8::new();
+    let date_ctor: Function = mv8.eval("Date").unwrap();
+    let error_ctor: Function = mv8.eval("Error").unwrap();
+    let point_ctor: Function = mv8.eval("class Point {}; Point").unwrap();
+
+    let date: Value = mv8.eval("new Date()").unwrap();
+    assert!(date.instance_of(&date_ctor, &mv8).unwrap());
+    assert!(!date.instance_of(&error_ctor, &mv8).unwrap());
+    let point: Value = mv8.eval("new Point()").unwrap();
+    assert!(point.instance_of(&point_ctor, &mv8).unwrap());
+    assert!(!Value::Number(1.0).instance_of(&date_ctor, &mv8).unwrap());
+    assert!(!Value::Null.instance_of(&date_ctor, &mv8).unwrap());
+}
+
+#[test]
+fn js_typeof() {
+    let mv8 = MiniV8::new();
+    let values: Vec<Value> = mv8.eval(r#"[
+        undefined, null, true, 1, 1n, new Date(), "a", Symbol(), [], function() {}, class {},
+        new Proxy(function() {}, {}), {}, Promise.resolve(), /a/,
+    ]"#).unwrap();
+    let types = values.iter().map(|v| v.js_typeof(&mv8)).collect::<Vec<_>>();
+    assert_eq!(types, vec![
+        "undefined", "object", "boolean", "number", "bigint", "object", "string", "symbol",
+        "object", "function", "function", "function", "object", "object", "object",
+    ]);
+}
+
+#[test]
+fn number_to_string_radix() {
+    let mv8 = MiniV8::new();
+    let format = |number: f64, radix: u32| {
+        Value::Number(number).number_to_string_radix(&mv8, radix).unwrap().to_string()
+    };
+    assert_eq!(format(255.0, 16), "ff");
+    assert_eq!(format(255.0, 16), mv8.eval::<_, StdString>("(255).toString(16)").unwrap());
+    assert_eq!(format(-255.5, 16), "-ff.8");
+    assert_eq!(format(5.0, 2), "101");
+    assert!(Value::Number(1.0).number_to_string_radix(&mv8, 37).is_err());
+    assert!(Value::Boolean(true).number_to_string_radix(&mv8, 10).is_err());
+}
+
+#[test]
+fn leak() {
+    let mv8 = MiniV8::new();
+    let object: Value = mv8.eval("globalThis.pinned = { name: 'pinned' }").unwrap();
+    let pinned = object.leak(&mv8);
+    mv8.eval::<_, ()>("delete globalThis.pinned").unwrap();
+    mv8.low_memory_notification();
+    let object: Object = pinned.get().into(&mv8).unwrap();
+    assert_eq!(object.get::<_, StdString>("name").unwrap(), "pinned");
+
+    // The pin keeps the value and its `MiniV8` alive after every other handle is dropped:
+    let ptr = pinned.into_raw();
+    drop(object);
+    drop(mv8);
+    let pinned = unsafe { PinnedValue::from_raw(ptr) };
+    let object = pinned.release();
+    assert_eq!(object.as_object().unwrap().get::<_, StdString>("name").unwrap(), "pinned");
+
+    let mv8 = MiniV8::new();
+    assert_eq!(Value::Number(1.0).leak(&mv8).get().as_number(), Some(1.0));
+}
+
+#[test]
+fn equality() {
+    let mv8 = MiniV8::new();
+    let nan = Value::Number(f64::NAN);
+    assert!(!nan.strict_equals(&nan, &mv8));
+    assert!(!nan.loose_equals(&nan, &mv8).unwrap());
+
+    let one = Value::Number(1.0);
+    let one_string = Value::String(mv8.create_string("1"));
+    assert!(!one.strict_equals(&one_string, &mv8));
+    assert!(one.loose_equals(&one_string, &mv8).unwrap());
+    assert!(Value::Null.loose_equals(&Value::Undefined, &mv8).unwrap());
+    assert!(!Value::Null.strict_equals(&Value::Undefined, &mv8));
+
+    let object = Value::Object(mv8.create_object());
+    assert!(object.strict_equals(&object.clone(), &mv8));
+    assert!(!object.strict_equals(&Value::Object(mv8.create_object()), &mv8));
+    let string = Value::String(mv8.create_string("[object Object]"));
+    assert!(object.loose_equals(&string, &mv8).unwrap());
+
+    let throwing: Value = mv8.eval("({ valueOf() { throw new Error('oops'); } })").unwrap();
+    assert!(throwing.loose_equals(&one, &mv8).is_err());
+}
+
+#[test]
+fn same_value() {
+    let mv8 = MiniV8::new();
+    let nan = Value::Number(f64::NAN);
+    assert!(nan.same_value(&nan, &mv8));
+    assert!(nan.same_value(&mv8.eval("0 / 0").unwrap(), &mv8));
+
+    let zero = Value::Number(0.0);
+    let negative_zero = Value::Number(-0.0);
+    assert!(zero.strict_equals(&negative_zero, &mv8));
+    assert!(!zero.same_value(&negative_zero, &mv8));
+    assert!(negative_zero.same_value(&mv8.eval("-0").unwrap(), &mv8));
+
+    let object = Value::Object(mv8.create_object());
+    assert!(object.same_value(&object.clone(), &mv8));
+    assert!(!object.same_value(&Value::Object(mv8.create_object()), &mv8));
+    assert!(!Value::Number(1.0).same_value(&Value::String(mv8.create_string("1")), &mv8));
+}
+
+#[test]
+fn is_nan_number() {
+    let mv8 = MiniV8::new();
+    assert!(mv8.eval::<_, Value>("NaN").unwrap().is_nan_number());
+    assert!(Value::Number(f64::NAN).is_nan_number());
+    assert!(!Value::Number(1.0).is_nan_number());
+
+    let string = Value::String(mv8.create_string("abc"));
+    assert!(string.coerce_number(&mv8).unwrap().is_nan());
+    assert!(!string.is_nan_number());
+    assert!(!Value::Undefined.is_nan_number());
+}
+
+#[test]
+fn serialize() {
+    let mv8 = MiniV8::new();
+    let value: Value = mv8.eval(r#"
+        const map = new Map([['a', 1], [2, new Date(1000)]]);
+        map.set('self', map);
+        ({ map, bytes: new Uint8Array([1, 2, 3]), missing: undefined, big: 10n ** 20n })
+    "#).unwrap();
+    let data = value.serialize(&mv8).unwrap();
+
+    let other = MiniV8::new();
+    let copy = other.deserialize(&data).unwrap();
+    other.global().set("copy", copy).unwrap();
+    let checks: Vec<bool> = other.eval(r#"[
+        copy.map instanceof Map,
+        copy.map.get('a') === 1,
+        copy.map.get(2).getTime() === 1000,
+        copy.map.get('self') === copy.map,
+        copy.bytes instanceof Uint8Array && copy.bytes.join() === '1,2,3',
+        'missing' in copy && copy.missing === undefined,
+        copy.big === 10n ** 20n,
+    ]"#).unwrap();
+    assert!(checks.into_iter().all(|check| check));
+
+    let function: Value = mv8.eval("({ f() {} })").unwrap();
+    match function.serialize(&mv8) {
+        Err(Error::Value(Value::Object(error))) => {
+            assert_eq!(error.get::<_, StdString>("name").unwrap(), "Error");
+        },
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert!(mv8.deserialize(&[1, 2, 3]).is_err());
+}
+
+#[test]
+fn compare_to_literals() {
+    let mv8 = MiniV8::new();
+    let number: Value = mv8.eval("2 + 2").unwrap();
+    assert_eq!(number, 4.0);
+    assert!(number != 5.0);
+    let nan = f64::NAN;
+    assert!(Value::Number(nan) != nan);
+
+    let string: Value = mv8.eval("'a' + 'bc'").unwrap();
+    assert_eq!(string, "abc");
+    assert!(string != "ab");
+    // There is no coercion:
+    assert!(Value::String(mv8.create_string("4")) != 4.0);
+    assert!(number != "4");
+
+    assert_eq!(mv8.eval::<_, Value>("1 < 2").unwrap(), true);
+    assert!(Value::Number(1.0) != true);
+}
+
+#[test]
+fn deep_equals() {
+    let mv8 = MiniV8::new();
+    let a: Value = mv8.eval("({ x: [1, 2], d: new Date(5), o: { y: null } })").unwrap();
+    let b: Value = mv8.eval("({ o: { y: null }, d: new Date(5), x: [1, 2] })").unwrap();
+    let c: Value = mv8.eval("({ x: [1, 2], d: new Date(6), o: { y: null } })").unwrap();
+    assert!(a.deep_equals(&b, &mv8).unwrap());
+    assert!(!a.deep_equals(&c, &mv8).unwrap());
+    assert!(!Value::Number(0.0).deep_equals(&Value::Number(-0.0), &mv8).unwrap());
+
+    let cyclic: Value = mv8.eval("var a = { n: 1 }; a.self = a; a").unwrap();
+    let other: Value = mv8.eval("var b = { n: 1 }; b.self = b; b").unwrap();
+    assert!(cyclic.deep_equals(&other, &mv8).unwrap());
+
+    let f: Value = mv8.eval("() => {}").unwrap();
+    let g: Value = mv8.eval("() => {}").unwrap();
+    assert!(f.deep_equals(&f, &mv8).unwrap());
+    assert!(!f.deep_equals(&g, &mv8).unwrap());
+
+    let getter: Value = mv8.eval("({ get x() { throw 'boom'; } })").unwrap();
+    assert!(getter.deep_equals(&getter.clone(), &mv8).unwrap());
+    let plain: Value = mv8.eval("({ x: 1 })").unwrap();
+    assert!(getter.deep_equals(&plain, &mv8).is_err());
+}
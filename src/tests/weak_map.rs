@@ -0,0 +1,32 @@
+use crate::*;
+use std::string::String as StdString;
+
+#[test]
+fn set_get() {
+    let mv8 = MiniV8::new();
+    let weak_map = mv8.create_weak_map();
+    let key = mv8.create_object();
+    weak_map.set(key.clone(), "value").unwrap();
+    assert!(weak_map.has(key.clone()).unwrap());
+    assert_eq!(weak_map.get::<_, StdString>(key.clone()).unwrap(), "value");
+    assert!(weak_map.get::<_, Value>(mv8.create_object()).unwrap().is_undefined());
+    assert!(weak_map.remove(key.clone()).unwrap());
+    assert!(!weak_map.has(key).unwrap());
+
+    match weak_map.set(1, 2) {
+        Err(Error::Value(Value::Object(error))) => {
+            assert_eq!(error.get::<_, StdString>("name").unwrap(), "TypeError");
+        },
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn from_js() {
+    let mv8 = MiniV8::new();
+    let value: Value = mv8.eval("const key = {}; globalThis.key = key; new WeakMap([[key, 1]])")
+        .unwrap();
+    assert_eq!(value.kind(), ValueKind::WeakMap);
+    let key: Object = mv8.global().get("key").unwrap();
+    assert_eq!(value.as_weak_map().unwrap().get::<_, i32>(key).unwrap(), 1);
+}
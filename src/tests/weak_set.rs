@@ -0,0 +1,17 @@
+use crate::*;
+
+#[test]
+fn add_has() {
+    let mv8 = MiniV8::new();
+    let weak_set = mv8.create_weak_set();
+    let value = mv8.create_object();
+    assert!(!weak_set.has(value.clone()).unwrap());
+    weak_set.add(value.clone()).unwrap();
+    assert!(weak_set.has(value.clone()).unwrap());
+    assert!(weak_set.remove(value.clone()).unwrap());
+    assert!(!weak_set.remove(value).unwrap());
+    assert!(weak_set.add(1).is_err());
+
+    let value: Value = mv8.eval("new WeakSet()").unwrap();
+    assert!(value.is_weak_set());
+}
@@ -0,0 +1,179 @@
+use crate::*;
+use std::any::type_name;
+use std::fmt;
+
+/// Reference to a JavaScript typed array (e.g. `Uint8Array` or `Float64Array`), a view of an
+/// `ArrayBuffer` as a list of numbers of a single kind.
+#[derive(Clone)]
+pub struct TypedArray {
+    pub(crate) mv8: MiniV8,
+    pub(crate) handle: v8::Global<v8::TypedArray>,
+}
+
+impl TypedArray {
+    /// Consumes the typed array and downgrades it to a JavaScript object.
+    pub fn into_object(self) -> Object {
+        self.mv8.clone().scope(|scope| {
+            let object: v8::Local<v8::Object> = v8::Local::new(scope, self.handle.clone()).into();
+            Object {
+                mv8: self.mv8,
+                handle: v8::Global::new(scope, object),
+            }
+        })
+    }
+
+    /// Returns the kind of elements held by the typed array.
+    pub fn kind(&self) -> TypedArrayKind {
+        self.mv8.scope(|scope| {
+            let value: v8::Local<v8::Value> = v8::Local::new(scope, self.handle.clone()).into();
+            TypedArrayKind::of(value)
+        })
+    }
+
+    /// Returns the number of elements in the typed array.
+    pub fn len(&self) -> usize {
+        self.mv8.scope(|scope| v8::Local::new(scope, self.handle.clone()).length())
+    }
+
+    /// Returns `true` if the typed array has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the length of the typed array in bytes.
+    pub fn byte_length(&self) -> usize {
+        self.mv8.scope(|scope| v8::Local::new(scope, self.handle.clone()).byte_length())
+    }
+
+    /// Returns the `ArrayBuffer` viewed by the typed array.
+    pub fn buffer(&self) -> ArrayBuffer {
+        self.mv8.scope(|scope| {
+            let buffer = v8::Local::new(scope, self.handle.clone()).buffer(scope).unwrap();
+            ArrayBuffer {
+                mv8: self.mv8.clone(),
+                handle: v8::Global::new(scope, buffer),
+            }
+        })
+    }
+
+    /// Returns a copy of the typed array's elements.
+    ///
+    /// Returns an error if `T` does not match the kind of the typed array's elements (e.g. when
+    /// reading a `Float64Array` into a `Vec<u8>`).
+    pub fn copy_to_vec<T: TypedArrayElement>(&self) -> Result<Vec<T>> {
+        let kind = self.kind();
+        if !T::KINDS.contains(&kind) {
+            return Err(Error::from_js_conversion(kind.name(), type_name::<T>()));
+        }
+
+        let bytes = self.mv8.scope(|scope| {
+            let array = v8::Local::new(scope, self.handle.clone());
+            let mut bytes = vec![0; array.byte_length()];
+            array.copy_contents(&mut bytes);
+            bytes
+        });
+        Ok(bytes.chunks_exact(T::SIZE).map(T::from_ne_bytes).collect())
+    }
+}
+
+impl fmt::Debug for TypedArray {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<{} of {} elements>", self.kind().name(), self.len())
+    }
+}
+
+/// The kind of elements held by a `TypedArray`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TypedArrayKind {
+    Int8,
+    Uint8,
+    Uint8Clamped,
+    Int16,
+    Uint16,
+    Int32,
+    Uint32,
+    Float32,
+    Float64,
+    BigInt64,
+    BigUint64,
+}
+
+impl TypedArrayKind {
+    /// Returns the name of the JavaScript constructor for this kind of typed array, e.g.
+    /// `"Uint8Array"`.
+    pub fn name(&self) -> &'static str {
+        match *self {
+            TypedArrayKind::Int8 => "Int8Array",
+            TypedArrayKind::Uint8 => "Uint8Array",
+            TypedArrayKind::Uint8Clamped => "Uint8ClampedArray",
+            TypedArrayKind::Int16 => "Int16Array",
+            TypedArrayKind::Uint16 => "Uint16Array",
+            TypedArrayKind::Int32 => "Int32Array",
+            TypedArrayKind::Uint32 => "Uint32Array",
+            TypedArrayKind::Float32 => "Float32Array",
+            TypedArrayKind::Float64 => "Float64Array",
+            TypedArrayKind::BigInt64 => "BigInt64Array",
+            TypedArrayKind::BigUint64 => "BigUint64Array",
+        }
+    }
+
+    fn of(value: v8::Local<v8::Value>) -> TypedArrayKind {
+        if value.is_int8_array() {
+            TypedArrayKind::Int8
+        } else if value.is_uint8_array() {
+            TypedArrayKind::Uint8
+        } else if value.is_uint8_clamped_array() {
+            TypedArrayKind::Uint8Clamped
+        } else if value.is_int16_array() {
+            TypedArrayKind::Int16
+        } else if value.is_uint16_array() {
+            TypedArrayKind::Uint16
+        } else if value.is_int32_array() {
+            TypedArrayKind::Int32
+        } else if value.is_uint32_array() {
+            TypedArrayKind::Uint32
+        } else if value.is_float32_array() {
+            TypedArrayKind::Float32
+        } else if value.is_float64_array() {
+            TypedArrayKind::Float64
+        } else if value.is_big_int64_array() {
+            TypedArrayKind::BigInt64
+        } else {
+            TypedArrayKind::BigUint64
+        }
+    }
+}
+
+/// Trait for Rust number types that can be read from the elements of a `TypedArray`.
+pub trait TypedArrayElement: Sized {
+    /// The kinds of typed arrays whose elements are of this type.
+    const KINDS: &'static [TypedArrayKind];
+    #[doc(hidden)]
+    const SIZE: usize;
+    #[doc(hidden)]
+    fn from_ne_bytes(bytes: &[u8]) -> Self;
+}
+
+macro_rules! typed_array_element {
+    ($prim_ty: ty, $($kind: ident),*) => {
+        impl TypedArrayElement for $prim_ty {
+            const KINDS: &'static [TypedArrayKind] = &[$(TypedArrayKind::$kind),*];
+            const SIZE: usize = std::mem::size_of::<$prim_ty>();
+
+            fn from_ne_bytes(bytes: &[u8]) -> Self {
+                <$prim_ty>::from_ne_bytes(bytes.try_into().unwrap())
+            }
+        }
+    }
+}
+
+typed_array_element!(i8, Int8);
+typed_array_element!(u8, Uint8, Uint8Clamped);
+typed_array_element!(i16, Int16);
+typed_array_element!(u16, Uint16);
+typed_array_element!(i32, Int32);
+typed_array_element!(u32, Uint32);
+typed_array_element!(f32, Float32);
+typed_array_element!(f64, Float64);
+typed_array_element!(i64, BigInt64);
+typed_array_element!(u64, BigUint64);
@@ -1,7 +1,10 @@
 use crate::*;
+use std::cell::Cell;
+use std::ffi::c_void;
 use std::iter::FromIterator;
-use std::ops::{Deref, DerefMut};
+use std::ops::{Deref, DerefMut, Range};
 use std::{fmt, slice, vec};
+use v8::ValueSerializerHelper;
 
 /// A JavaScript value.
 ///
@@ -19,63 +22,141 @@ pub enum Value {
     Boolean(bool),
     /// A JavaScript floating point number.
     Number(f64),
+    /// Reference to a JavaScript `BigInt`, an integer of arbitrary size.
+    BigInt(BigInt),
     /// Elapsed milliseconds since Unix epoch.
     Date(f64),
     /// An immutable JavaScript string, managed by V8.
     String(String),
+    /// Reference to a JavaScript symbol.
+    Symbol(Symbol),
     /// Reference to a JavaScript arrray.
     Array(Array),
+    /// Reference to a JavaScript `ArrayBuffer`.
+    ArrayBuffer(ArrayBuffer),
+    /// Reference to a JavaScript typed array, such as a `Uint8Array`.
+    TypedArray(TypedArray),
     /// Reference to a JavaScript function.
     Function(Function),
-    /// Reference to a JavaScript object. If a value is a function or an array in JavaScript, it
-    /// will be converted to `Value::Array` or `Value::Function` instead of `Value::Object`.
+    /// Reference to a JavaScript promise.
+    Promise(Promise),
+    /// Reference to a JavaScript `Map`.
+    Map(Map),
+    /// Reference to a JavaScript `Set`.
+    Set(Set),
+    /// Reference to a JavaScript `WeakMap`.
+    WeakMap(WeakMap),
+    /// Reference to a JavaScript `WeakSet`.
+    WeakSet(WeakSet),
+    /// Reference to a JavaScript regular expression.
+    RegExp(RegExp),
+    /// Reference to a JavaScript object. If a value is an array, an array buffer, a typed array, a
+    /// function, a promise, a map, a set, a weak map, a weak set, or a regular expression in
+    /// JavaScript, it will be converted to the corresponding variant instead of `Value::Object`.
     Object(Object),
 }
 
 impl Value {
     /// Returns `true` if this is a `Value::Undefined`, `false` otherwise.
     pub fn is_undefined(&self) -> bool {
-        if let Value::Undefined = *self { true } else { false }
+        matches!(*self, Value::Undefined)
     }
 
     /// Returns `true` if this is a `Value::Null`, `false` otherwise.
     pub fn is_null(&self) -> bool {
-        if let Value::Null = *self { true } else { false }
+        matches!(*self, Value::Null)
     }
 
     /// Returns `true` if this is a `Value::Boolean`, `false` otherwise.
     pub fn is_boolean(&self) -> bool {
-        if let Value::Boolean(_) = *self { true } else { false }
+        matches!(*self, Value::Boolean(_))
     }
 
     /// Returns `true` if this is a `Value::Number`, `false` otherwise.
     pub fn is_number(&self) -> bool {
-        if let Value::Number(_) = *self { true } else { false }
+        matches!(*self, Value::Number(_))
+    }
+
+    /// Returns `true` if this is a `Value::Number` holding `NaN`, `false` otherwise. Unlike
+    /// checking the result of `Value::coerce_number`, this does not treat values that merely
+    /// coerce to `NaN` (e.g. `"abc"` or `undefined`) as `NaN`.
+    pub fn is_nan_number(&self) -> bool {
+        if let Value::Number(n) = *self { n.is_nan() } else { false }
+    }
+
+    /// Returns `true` if this is a `Value::BigInt`, `false` otherwise.
+    pub fn is_bigint(&self) -> bool {
+        matches!(*self, Value::BigInt(_))
     }
 
     /// Returns `true` if this is a `Value::Date`, `false` otherwise.
     pub fn is_date(&self) -> bool {
-        if let Value::Date(_) = *self { true } else { false }
+        matches!(*self, Value::Date(_))
     }
 
     /// Returns `true` if this is a `Value::String`, `false` otherwise.
     pub fn is_string(&self) -> bool {
-        if let Value::String(_) = *self { true } else { false }
+        matches!(*self, Value::String(_))
+    }
+
+    /// Returns `true` if this is a `Value::Symbol`, `false` otherwise.
+    pub fn is_symbol(&self) -> bool {
+        matches!(*self, Value::Symbol(_))
     }
 
     /// Returns `true` if this is a `Value::Array`, `false` otherwise.
     pub fn is_array(&self) -> bool {
-        if let Value::Array(_) = *self { true } else { false }
+        matches!(*self, Value::Array(_))
+    }
+
+    /// Returns `true` if this is a `Value::ArrayBuffer`, `false` otherwise.
+    pub fn is_array_buffer(&self) -> bool {
+        matches!(*self, Value::ArrayBuffer(_))
+    }
+
+    /// Returns `true` if this is a `Value::TypedArray`, `false` otherwise.
+    pub fn is_typed_array(&self) -> bool {
+        matches!(*self, Value::TypedArray(_))
     }
 
     /// Returns `true` if this is a `Value::Function`, `false` otherwise.
     pub fn is_function(&self) -> bool {
-        if let Value::Function(_) = *self { true } else { false }
+        matches!(*self, Value::Function(_))
+    }
+
+    /// Returns `true` if this is a `Value::Promise`, `false` otherwise.
+    pub fn is_promise(&self) -> bool {
+        matches!(*self, Value::Promise(_))
+    }
+
+    /// Returns `true` if this is a `Value::Map`, `false` otherwise.
+    pub fn is_map(&self) -> bool {
+        matches!(*self, Value::Map(_))
+    }
+
+    /// Returns `true` if this is a `Value::Set`, `false` otherwise.
+    pub fn is_set(&self) -> bool {
+        matches!(*self, Value::Set(_))
+    }
+
+    /// Returns `true` if this is a `Value::WeakMap`, `false` otherwise.
+    pub fn is_weak_map(&self) -> bool {
+        matches!(*self, Value::WeakMap(_))
+    }
+
+    /// Returns `true` if this is a `Value::WeakSet`, `false` otherwise.
+    pub fn is_weak_set(&self) -> bool {
+        matches!(*self, Value::WeakSet(_))
+    }
+
+    /// Returns `true` if this is a `Value::RegExp`, `false` otherwise.
+    pub fn is_regexp(&self) -> bool {
+        matches!(*self, Value::RegExp(_))
     }
 
     /// Returns `true` if this is a `Value::Object`, `false` otherwise.
     pub fn is_object(&self) -> bool {
-        if let Value::Object(_) = *self { true } else { false }
+        matches!(*self, Value::Object(_))
     }
 
     /// Returns `Some(())` if this is a `Value::Undefined`, `None` otherwise.
@@ -98,31 +179,141 @@ impl Value {
         if let Value::Number(value) = *self { Some(value) } else { None }
     }
 
+    /// Returns `Some` if this is a `Value::BigInt`, `None` otherwise.
+    pub fn as_bigint(&self) -> Option<&BigInt> {
+        if let Value::BigInt(ref value) = *self { Some(value) } else { None }
+    }
+
     /// Returns `Some` if this is a `Value::Date`, `None` otherwise.
     pub fn as_date(&self) -> Option<f64> {
         if let Value::Date(value) = *self { Some(value) } else { None }
     }
 
+    /// Returns `Some` if this is a `Value::Date` holding a valid date, `None` otherwise. Any
+    /// sub-millisecond part of the timestamp is truncated.
+    #[cfg(feature = "chrono")]
+    pub fn as_chrono(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        use chrono::TimeZone;
+        match *self {
+            Value::Date(value) if value.is_finite() => {
+                chrono::Utc.timestamp_millis_opt(value.trunc() as i64).single()
+            },
+            _ => None,
+        }
+    }
+
     /// Returns `Some` if this is a `Value::String`, `None` otherwise.
     pub fn as_string(&self) -> Option<&String> {
         if let Value::String(ref value) = *self { Some(value) } else { None }
     }
 
+    /// Returns `Some` if this is a `Value::Symbol`, `None` otherwise.
+    pub fn as_symbol(&self) -> Option<&Symbol> {
+        if let Value::Symbol(ref value) = *self { Some(value) } else { None }
+    }
+
     /// Returns `Some` if this is a `Value::Array`, `None` otherwise.
     pub fn as_array(&self) -> Option<&Array> {
         if let Value::Array(ref value) = *self { Some(value) } else { None }
     }
 
+    /// Returns `Some` if this is a `Value::ArrayBuffer`, `None` otherwise.
+    pub fn as_array_buffer(&self) -> Option<&ArrayBuffer> {
+        if let Value::ArrayBuffer(ref value) = *self { Some(value) } else { None }
+    }
+
+    /// Returns `Some` if this is a `Value::TypedArray`, `None` otherwise.
+    pub fn as_typed_array(&self) -> Option<&TypedArray> {
+        if let Value::TypedArray(ref value) = *self { Some(value) } else { None }
+    }
+
     /// Returns `Some` if this is a `Value::Function`, `None` otherwise.
     pub fn as_function(&self) -> Option<&Function> {
         if let Value::Function(ref value) = *self { Some(value) } else { None }
     }
 
+    /// Returns `Some` if this is a `Value::Promise`, `None` otherwise.
+    pub fn as_promise(&self) -> Option<&Promise> {
+        if let Value::Promise(ref value) = *self { Some(value) } else { None }
+    }
+
+    /// Returns `Some` if this is a `Value::Map`, `None` otherwise.
+    pub fn as_map(&self) -> Option<&Map> {
+        if let Value::Map(ref value) = *self { Some(value) } else { None }
+    }
+
+    /// Returns `Some` if this is a `Value::Set`, `None` otherwise.
+    pub fn as_set(&self) -> Option<&Set> {
+        if let Value::Set(ref value) = *self { Some(value) } else { None }
+    }
+
+    /// Returns `Some` if this is a `Value::WeakMap`, `None` otherwise.
+    pub fn as_weak_map(&self) -> Option<&WeakMap> {
+        if let Value::WeakMap(ref value) = *self { Some(value) } else { None }
+    }
+
+    /// Returns `Some` if this is a `Value::WeakSet`, `None` otherwise.
+    pub fn as_weak_set(&self) -> Option<&WeakSet> {
+        if let Value::WeakSet(ref value) = *self { Some(value) } else { None }
+    }
+
+    /// Returns `Some` if this is a `Value::RegExp`, `None` otherwise.
+    pub fn as_regexp(&self) -> Option<&RegExp> {
+        if let Value::RegExp(ref value) = *self { Some(value) } else { None }
+    }
+
     /// Returns `Some` if this is a `Value::Object`, `None` otherwise.
     pub fn as_object(&self) -> Option<&Object> {
         if let Value::Object(ref value) = *self { Some(value) } else { None }
     }
 
+    /// Returns the kind of this value, for matching on a value's type without inspecting its
+    /// contents.
+    pub fn kind(&self) -> ValueKind {
+        match *self {
+            Value::Undefined => ValueKind::Undefined,
+            Value::Null => ValueKind::Null,
+            Value::Boolean(_) => ValueKind::Boolean,
+            Value::Number(_) => ValueKind::Number,
+            Value::BigInt(_) => ValueKind::BigInt,
+            Value::Date(_) => ValueKind::Date,
+            Value::String(_) => ValueKind::String,
+            Value::Symbol(_) => ValueKind::Symbol,
+            Value::Array(_) => ValueKind::Array,
+            Value::ArrayBuffer(_) => ValueKind::ArrayBuffer,
+            Value::TypedArray(_) => ValueKind::TypedArray,
+            Value::Function(_) => ValueKind::Function,
+            Value::Promise(_) => ValueKind::Promise,
+            Value::Map(_) => ValueKind::Map,
+            Value::Set(_) => ValueKind::Set,
+            Value::WeakMap(_) => ValueKind::WeakMap,
+            Value::WeakSet(_) => ValueKind::WeakSet,
+            Value::RegExp(_) => ValueKind::RegExp,
+            Value::Object(_) => ValueKind::Object,
+        }
+    }
+
+    /// Creates a weak reference to the value, which does not keep it from being garbage collected.
+    pub fn downgrade(&self) -> WeakValue {
+        match self {
+            Value::BigInt(v) => WeakValue::new(&v.mv8, self),
+            Value::String(v) => WeakValue::new(&v.mv8, self),
+            Value::Symbol(v) => WeakValue::new(&v.mv8, self),
+            Value::Array(v) => WeakValue::new(&v.mv8, self),
+            Value::ArrayBuffer(v) => WeakValue::new(&v.mv8, self),
+            Value::TypedArray(v) => WeakValue::new(&v.mv8, self),
+            Value::Function(v) => WeakValue::new(&v.mv8, self),
+            Value::Promise(v) => WeakValue::new(&v.mv8, self),
+            Value::Map(v) => WeakValue::new(&v.mv8, self),
+            Value::Set(v) => WeakValue::new(&v.mv8, self),
+            Value::WeakMap(v) => WeakValue::new(&v.mv8, self),
+            Value::WeakSet(v) => WeakValue::new(&v.mv8, self),
+            Value::RegExp(v) => WeakValue::new(&v.mv8, self),
+            Value::Object(v) => WeakValue::new(&v.mv8, self),
+            value => WeakValue::direct(value.clone()),
+        }
+    }
+
     /// A wrapper around `FromValue::from_value`.
     pub fn into<T: FromValue>(self, mv8: &MiniV8) -> Result< T> {
         T::from_value(self, mv8)
@@ -140,7 +331,9 @@ impl Value {
     /// may fail with a runtime error under extraordinary circumstances (e.g. if the ECMAScript
     /// `ToNumber` implementation throws an error).
     ///
-    /// This will return `std::f64::NAN` if the value has no numerical equivalent.
+    /// This will return `std::f64::NAN` if the value has no numerical equivalent (e.g. `"abc"`,
+    /// `undefined` or most objects), so a `NaN` result does not mean that the value itself was
+    /// `NaN`. Use `Value::is_nan_number` to tell the two apart.
     pub fn coerce_number(&self, mv8: &MiniV8) -> Result<f64> {
         match self {
             &Value::Number(n) => Ok(n),
@@ -151,33 +344,228 @@ impl Value {
         }
     }
 
+    /// Coerces a value to a number like `Value::coerce_number`, then to an integer. Unlike a Rust
+    /// `as` cast, this never loses information: it returns an `Error::FromJsConversionError` if the
+    /// number is `NaN`, infinite, has a fractional part, or is outside the range of `i64`.
+    pub fn coerce_integer(&self, mv8: &MiniV8) -> Result<i64> {
+        // `i64::MAX as f64` rounds to exactly `2 ** 63`, which is out of range:
+        let range = i64::MIN as f64..i64::MAX as f64;
+        self.coerce_integral(mv8, range, "integer").map(|number| number as i64)
+    }
+
+    // Coerces a value to a number that must be an integer within `range`, or else returns an
+    // `Error::FromJsConversionError` naming `to`. `NaN` and infinities fail the `fract` check. This
+    // is the one check behind `coerce_integer` and the strict and `Checked` integer conversions.
+    pub(crate) fn coerce_integral(
+        &self,
+        mv8: &MiniV8,
+        range: Range<f64>,
+        to: &'static str,
+    ) -> Result<f64> {
+        let number = self.coerce_number(mv8)?;
+        if number.fract() == 0.0 && range.contains(&number) {
+            Ok(number)
+        } else {
+            Err(Error::from_js_conversion(self.type_name(), to))
+        }
+    }
+
     /// Coerces a value to a string. Nearly all JavaScript values are coercible to strings, but this
     /// may fail with a runtime error if `toString()` fails or under otherwise extraordinary
     /// circumstances (e.g. if the ECMAScript `ToString` implementation throws an error).
     pub fn coerce_string(&self, mv8: &MiniV8) -> Result<String> {
         match self {
-            &Value::String(ref s) => Ok(s.clone()),
+            Value::String(s) => Ok(s.clone()),
             value => mv8.try_catch(|scope| {
                 let maybe = value.to_v8_value(scope).to_string(scope);
                 mv8.exception(scope).map(|_| String {
                     mv8: mv8.clone(),
                     handle: v8::Global::new(scope, maybe.unwrap()),
+                    cache: Default::default(),
                 })
             }),
         }
     }
 
+    /// Serializes a value into a JSON string, as JavaScript's `JSON.stringify` would.
+    ///
+    /// Returns an `Error::Value` holding a JavaScript `TypeError` if the value contains a cycle, or
+    /// any error thrown by a `toJSON` method of the value.
+    pub fn to_json(&self, mv8: &MiniV8) -> Result<String> {
+        mv8.try_catch(|scope| {
+            let value = self.to_v8_value(scope);
+            let json = v8::json::stringify(scope, value);
+            mv8.exception(scope).map(|_| String {
+                mv8: mv8.clone(),
+                handle: v8::Global::new(scope, json.unwrap()),
+                cache: Default::default(),
+            })
+        })
+    }
+
+    /// Serializes a value into a JSON string like `Value::to_json`, indenting nested values by
+    /// `space` spaces (up to 10, like JavaScript's `JSON.stringify`).
+    pub fn to_json_pretty(&self, mv8: &MiniV8, space: usize) -> Result<String> {
+        let stringify = mv8.builtin(|builtins| &builtins.json_stringify);
+        let json: Value = stringify.call((self.clone(), Value::Null, space))?;
+        json.coerce_string(mv8)
+    }
+
+    /// Serializes a value with V8's structured serialization format (the format behind
+    /// `structuredClone` and `postMessage`), which can be deserialized with `MiniV8::deserialize`,
+    /// including in another `MiniV8`. Unlike JSON, this preserves `Map`s, `Set`s, dates, regular
+    /// expressions, typed arrays, `undefined`, `BigInt`s and cyclic references.
+    ///
+    /// Returns an `Error::Value` holding a JavaScript `Error` if the value is or contains a value
+    /// that can't be serialized, such as a function or symbol.
+    pub fn serialize(&self, mv8: &MiniV8) -> Result<Vec<u8>> {
+        mv8.try_catch(|scope| {
+            let value = self.to_v8_value(scope);
+            let context = scope.get_current_context();
+            let mut serializer = v8::ValueSerializer::new(scope, Box::new(Serializer));
+            serializer.write_header();
+            let written = serializer.write_value(context, value);
+            mv8.exception(scope)?;
+            match written {
+                Some(true) => Ok(serializer.release()),
+                _ => Err(Error::ExternalError("value could not be serialized".into())),
+            }
+        })
+    }
+
+    /// Formats a number in the given radix (from 2 to 36), exactly as JavaScript's
+    /// `Number.prototype.toString` would, including its handling of negative and fractional
+    /// numbers (e.g. `-255.5` in radix 16 is `"-ff.8"`).
+    ///
+    /// Returns an `Error::Value` holding a JavaScript `TypeError` if this is not a
+    /// `Value::Number`, or a `RangeError` if the radix is out of range.
+    pub fn number_to_string_radix(&self, mv8: &MiniV8, radix: u32) -> Result<String> {
+        let to_string = mv8.builtin(|builtins| &builtins.number_to_string);
+        to_string.call_method(self.clone(), (radix,))
+    }
+
+    /// Returns `true` if this value is equal to `other` per JavaScript's strict equality operator
+    /// (`===`). Reference types are compared by identity, and `NaN` is not equal to itself.
+    pub fn strict_equals(&self, other: &Value, mv8: &MiniV8) -> bool {
+        mv8.scope(|scope| {
+            let value = self.to_v8_value(scope);
+            let other = other.to_v8_value(scope);
+            value.strict_equals(other)
+        })
+    }
+
+    /// Returns `true` if this value is equal to `other` per JavaScript's `Object.is` (the SameValue
+    /// algorithm). This is like `Value::strict_equals`, except that `NaN` is equal to itself and
+    /// `+0` is not equal to `-0`.
+    pub fn same_value(&self, other: &Value, mv8: &MiniV8) -> bool {
+        mv8.scope(|scope| {
+            let value = self.to_v8_value(scope);
+            let other = other.to_v8_value(scope);
+            value.same_value(other)
+        })
+    }
+
+    /// Returns `true` if this value is structurally equal to `other`. Arrays are equal if they have
+    /// the same length and deeply equal elements, and other plain objects are equal if they have
+    /// the same own enumerable keys with deeply equal values (their prototypes are not compared).
+    /// Dates are equal if they hold the same time. Everything else, including functions and other
+    /// reference types, is compared per `Value::same_value`. Cyclic structures are supported.
+    ///
+    /// Returns an error if reading a property throws, e.g. from a getter.
+    pub fn deep_equals(&self, other: &Value, mv8: &MiniV8) -> Result<bool> {
+        deep_equals(self, other, mv8, &mut Vec::new())
+    }
+
+    /// Returns `true` if this value is equal to `other` per JavaScript's loose equality operator
+    /// (`==`), which coerces values of different types (e.g. `1 == "1"`).
+    ///
+    /// Returns an error if coercing either value throws, e.g. from a custom `valueOf` method.
+    pub fn loose_equals(&self, other: &Value, mv8: &MiniV8) -> Result<bool> {
+        let loose_equals = mv8.builtin(|builtins| &builtins.loose_equals);
+        loose_equals.call((self.clone(), other.clone()))
+    }
+
+    /// Returns the result of JavaScript's `typeof` operator for this value. Unlike the kinds
+    /// reported by `Value::kind`, this reports `null` as `"object"`, and all dates, arrays and
+    /// other special objects as either `"object"` or `"function"`.
+    pub fn js_typeof(&self, mv8: &MiniV8) -> &'static str {
+        match *self {
+            Value::Undefined => "undefined",
+            Value::Null => "object",
+            Value::Boolean(_) => "boolean",
+            Value::Number(_) => "number",
+            Value::BigInt(_) => "bigint",
+            Value::String(_) => "string",
+            Value::Symbol(_) => "symbol",
+            Value::Function(_) => "function",
+            // Plain objects may still be callable (e.g. proxies wrapping functions), so ask V8:
+            Value::Object(_) => mv8.scope(|scope| {
+                let value = self.to_v8_value(scope);
+                match value.type_of(scope).to_rust_string_lossy(scope).as_str() {
+                    "function" => "function",
+                    _ => "object",
+                }
+            }),
+            Value::Date(_) | Value::Array(_) | Value::ArrayBuffer(_) | Value::TypedArray(_) |
+            Value::Promise(_) | Value::Map(_) | Value::Set(_) | Value::WeakMap(_) |
+            Value::WeakSet(_) | Value::RegExp(_) => "object",
+        }
+    }
+
+    /// Returns `true` if this value is an instance of the given constructor, as JavaScript's
+    /// `instanceof` operator would. Returns `false` for primitive values (which are never
+    /// instances), rather than an error.
+    ///
+    /// Returns an error if the constructor is not callable in an `instanceof` check, or if its
+    /// `Symbol.hasInstance` method throws.
+    pub fn instance_of(&self, constructor: &Function, mv8: &MiniV8) -> Result<bool> {
+        match *self {
+            Value::Undefined | Value::Null | Value::Boolean(_) | Value::Number(_) |
+            Value::BigInt(_) | Value::String(_) | Value::Symbol(_) => return Ok(false),
+            _ => {},
+        }
+
+        mv8.try_catch(|scope| {
+            let value = self.to_v8_value(scope);
+            let constructor = v8::Local::new(scope, constructor.handle.clone());
+            let result = value.instance_of(scope, constructor.into());
+            mv8.exception(scope)?;
+            Ok(result.unwrap())
+        })
+    }
+
+    /// Pins the value with a persistent handle, keeping both the value and its `MiniV8` alive
+    /// until the returned `PinnedValue` is released or dropped, even after every other handle to
+    /// them has been dropped. See `PinnedValue::into_raw` for storing the pin outside of Rust,
+    /// e.g. in a C structure.
+    pub fn leak(self, mv8: &MiniV8) -> PinnedValue {
+        mv8.scope(|scope| {
+            let value = self.to_v8_value(scope);
+            PinnedValue { mv8: mv8.clone(), handle: v8::Global::new(scope, value) }
+        })
+    }
+
     pub(crate) fn type_name(&self) -> &'static str {
         match *self {
             Value::Undefined => "undefined",
             Value::Null => "null",
             Value::Boolean(_) => "boolean",
             Value::Number(_) => "number",
+            Value::BigInt(_) => "bigint",
             Value::Date(_) => "date",
             Value::Function(_) => "function",
+            Value::Promise(_) => "promise",
+            Value::Map(_) => "map",
+            Value::Set(_) => "set",
+            Value::WeakMap(_) => "weakmap",
+            Value::WeakSet(_) => "weakset",
+            Value::RegExp(_) => "regexp",
             Value::Array(_) => "array",
+            Value::ArrayBuffer(_) => "arraybuffer",
+            Value::TypedArray(_) => "typedarray",
             Value::Object(_) => "object",
             Value::String(_) => "string",
+            Value::Symbol(_) => "symbol",
         }
     }
 
@@ -196,21 +584,61 @@ impl Value {
             Value::Number(value.int32_value(scope).unwrap() as f64)
         } else if value.is_number() {
             Value::Number(value.number_value(scope).unwrap())
+        } else if value.is_big_int() {
+            let value: v8::Local<v8::BigInt> = value.try_into().unwrap();
+            let handle = v8::Global::new(scope, value);
+            Value::BigInt(BigInt { mv8: mv8.clone(), handle })
         } else if value.is_date() {
             let value: v8::Local<v8::Date> = value.try_into().unwrap();
             Value::Date(value.value_of())
         } else if value.is_string() {
             let value: v8::Local<v8::String> = value.try_into().unwrap();
             let handle = v8::Global::new(scope, value);
-            Value::String(String { mv8: mv8.clone(), handle })
+            Value::String(String { mv8: mv8.clone(), handle, cache: Default::default() })
+        } else if value.is_symbol() {
+            let value: v8::Local<v8::Symbol> = value.try_into().unwrap();
+            let handle = v8::Global::new(scope, value);
+            Value::Symbol(Symbol { mv8: mv8.clone(), handle })
         } else if value.is_array() {
             let value: v8::Local<v8::Array> = value.try_into().unwrap();
             let handle = v8::Global::new(scope, value);
             Value::Array(Array { mv8: mv8.clone(), handle })
+        } else if value.is_array_buffer() {
+            let value: v8::Local<v8::ArrayBuffer> = value.try_into().unwrap();
+            let handle = v8::Global::new(scope, value);
+            Value::ArrayBuffer(ArrayBuffer { mv8: mv8.clone(), handle })
+        } else if value.is_typed_array() {
+            let value: v8::Local<v8::TypedArray> = value.try_into().unwrap();
+            let handle = v8::Global::new(scope, value);
+            Value::TypedArray(TypedArray { mv8: mv8.clone(), handle })
         } else if value.is_function() {
             let value: v8::Local<v8::Function> = value.try_into().unwrap();
             let handle = v8::Global::new(scope, value);
             Value::Function(Function { mv8: mv8.clone(), handle })
+        } else if value.is_promise() {
+            let value: v8::Local<v8::Promise> = value.try_into().unwrap();
+            let handle = v8::Global::new(scope, value);
+            Value::Promise(Promise { mv8: mv8.clone(), handle })
+        } else if value.is_map() {
+            let value: v8::Local<v8::Map> = value.try_into().unwrap();
+            let handle = v8::Global::new(scope, value);
+            Value::Map(Map { mv8: mv8.clone(), handle })
+        } else if value.is_set() {
+            let value: v8::Local<v8::Set> = value.try_into().unwrap();
+            let handle = v8::Global::new(scope, value);
+            Value::Set(Set { mv8: mv8.clone(), handle })
+        } else if value.is_weak_map() {
+            let value: v8::Local<v8::Object> = value.try_into().unwrap();
+            let handle = v8::Global::new(scope, value);
+            Value::WeakMap(WeakMap { mv8: mv8.clone(), handle })
+        } else if value.is_weak_set() {
+            let value: v8::Local<v8::Object> = value.try_into().unwrap();
+            let handle = v8::Global::new(scope, value);
+            Value::WeakSet(WeakSet { mv8: mv8.clone(), handle })
+        } else if value.is_reg_exp() {
+            let value: v8::Local<v8::RegExp> = value.try_into().unwrap();
+            let handle = v8::Global::new(scope, value);
+            Value::RegExp(RegExp { mv8: mv8.clone(), handle })
         } else if value.is_object() {
             let value: v8::Local<v8::Object> = value.try_into().unwrap();
             let handle = v8::Global::new(scope, value);
@@ -228,12 +656,117 @@ impl Value {
             Value::Null => v8::null(scope).into(),
             Value::Boolean(v) => v8::Boolean::new(scope, *v).into(),
             Value::Number(v) => v8::Number::new(scope, *v).into(),
+            Value::BigInt(v) => v8::Local::new(scope, v.handle.clone()).into(),
             Value::Date(v) => v8::Date::new(scope, *v).unwrap().into(),
             Value::Function(v) => v8::Local::new(scope, v.handle.clone()).into(),
+            Value::Promise(v) => v8::Local::new(scope, v.handle.clone()).into(),
+            Value::Map(v) => v8::Local::new(scope, v.handle.clone()).into(),
+            Value::Set(v) => v8::Local::new(scope, v.handle.clone()).into(),
+            Value::WeakMap(v) => v8::Local::new(scope, v.handle.clone()).into(),
+            Value::WeakSet(v) => v8::Local::new(scope, v.handle.clone()).into(),
+            Value::RegExp(v) => v8::Local::new(scope, v.handle.clone()).into(),
             Value::Array(v) => v8::Local::new(scope, v.handle.clone()).into(),
+            Value::ArrayBuffer(v) => v8::Local::new(scope, v.handle.clone()).into(),
+            Value::TypedArray(v) => v8::Local::new(scope, v.handle.clone()).into(),
             Value::Object(v) => v8::Local::new(scope, v.handle.clone()).into(),
             Value::String(v) => v8::Local::new(scope, v.handle.clone()).into(),
+            Value::Symbol(v) => v8::Local::new(scope, v.handle.clone()).into(),
+        }
+    }
+}
+
+// Implements `Value::deep_equals`. `visiting` holds the pairs of objects currently being compared
+// further up the stack, which are assumed equal when encountered again so that cycles terminate.
+fn deep_equals(
+    value: &Value,
+    other: &Value,
+    mv8: &MiniV8,
+    visiting: &mut Vec<(Object, Object)>,
+) -> Result<bool> {
+    let (value, other) = match (value, other) {
+        (Value::Date(value), Value::Date(other)) => {
+            return Ok(value == other || (value.is_nan() && other.is_nan()));
+        }
+        (Value::Array(value), Value::Array(other)) if value.len() != other.len() => {
+            return Ok(false);
+        }
+        (Value::Array(value), Value::Array(other)) => {
+            (value.clone().into_object(), other.clone().into_object())
+        }
+        (Value::Object(value), Value::Object(other)) => (value.clone(), other.clone()),
+        (value, other) => return Ok(value.same_value(other, mv8)),
+    };
+
+    if value.ptr_eq(&other) || visiting.iter().any(|(a, b)| a.ptr_eq(&value) && b.ptr_eq(&other)) {
+        return Ok(true);
+    }
+
+    let keys: Vec<Value> = value.keys(false)?.to_vec()?;
+    let other_keys: Vec<Value> = other.keys(false)?.to_vec()?;
+    if keys.len() != other_keys.len() {
+        return Ok(false);
+    }
+
+    visiting.push((value.clone(), other.clone()));
+    let result = (|| {
+        for key in keys {
+            if !other_keys.iter().any(|other_key| other_key.same_value(&key, mv8)) {
+                return Ok(false);
+            }
+            let a: Value = value.get(key.clone())?;
+            let b: Value = other.get(key)?;
+            if !deep_equals(&a, &b, mv8, visiting)? {
+                return Ok(false);
+            }
         }
+        Ok(true)
+    })();
+    visiting.pop();
+    result
+}
+
+// The nesting depth at which `Debug` output of objects and arrays is elided, which also keeps the
+// output of cyclic structures finite.
+const MAX_DEBUG_DEPTH: usize = 8;
+
+thread_local! {
+    static DEBUG_DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+// Formats a nested object or array with `func`, or writes `elided` instead if the output is already
+// nested `MAX_DEBUG_DEPTH` levels deep. Exceptions caught while formatting are not reported by
+// `MiniV8::last_exception_details`.
+pub(crate) fn debug_nested<F>(
+    mv8: &MiniV8,
+    f: &mut fmt::Formatter,
+    elided: &str,
+    func: F,
+) -> fmt::Result
+where
+    F: FnOnce(&mut fmt::Formatter) -> fmt::Result,
+{
+    let depth = DEBUG_DEPTH.with(Cell::get);
+    if depth >= MAX_DEBUG_DEPTH {
+        return write!(f, "{}", elided);
+    }
+    DEBUG_DEPTH.with(|d| d.set(depth + 1));
+    let result = mv8.preserving_last_exception(|| func(f));
+    DEBUG_DEPTH.with(|d| d.set(depth));
+    result
+}
+
+// Delegate for `v8::ValueSerializer` that throws errors as plain JavaScript `Error`s, and
+// otherwise uses V8's default behavior (e.g. rejecting host objects).
+struct Serializer;
+
+impl v8::ValueSerializerImpl for Serializer {
+    fn throw_data_clone_error<'s>(
+        &mut self,
+        scope: &mut v8::HandleScope<'s>,
+        message: v8::Local<'s, v8::String>,
+    ) {
+        let error = v8::Exception::error(scope, message);
+        scope.throw_exception(error);
     }
 }
 
@@ -244,15 +777,123 @@ impl fmt::Debug for Value {
             Value::Null => write!(f, "null"),
             Value::Boolean(b) => write!(f, "{:?}", b),
             Value::Number(n) => write!(f, "{}", n),
+            Value::BigInt(b) => write!(f, "{:?}", b),
             Value::Date(d) => write!(f, "date:{}", d),
             Value::String(s) => write!(f, "{:?}", s),
+            Value::Symbol(s) => write!(f, "{:?}", s),
             Value::Array(a) => write!(f, "{:?}", a),
+            Value::ArrayBuffer(b) => write!(f, "{:?}", b),
+            Value::TypedArray(t) => write!(f, "{:?}", t),
             Value::Function(u) => write!(f, "{:?}", u),
+            Value::Promise(p) => write!(f, "{:?}", p),
+            Value::Map(m) => write!(f, "{:?}", m),
+            Value::Set(s) => write!(f, "{:?}", s),
+            Value::WeakMap(m) => write!(f, "{:?}", m),
+            Value::WeakSet(s) => write!(f, "{:?}", s),
+            Value::RegExp(r) => write!(f, "{:?}", r),
             Value::Object(o) => write!(f, "{:?}", o),
         }
     }
 }
 
+/// Compares a value to a number without coercion, so only a `Value::Number` can equal a number.
+/// As in both Rust and JavaScript, `NaN` is not equal to itself.
+impl PartialEq<f64> for Value {
+    fn eq(&self, other: &f64) -> bool {
+        self.as_number() == Some(*other)
+    }
+}
+
+/// Compares a value to a string without coercion, so only a `Value::String` can equal a string.
+/// This reads the contents of the V8 string.
+impl<'a> PartialEq<&'a str> for Value {
+    fn eq(&self, other: &&'a str) -> bool {
+        match self {
+            Value::String(string) => string.to_string() == *other,
+            _ => false,
+        }
+    }
+}
+
+/// Compares a value to a boolean without coercion, so only a `Value::Boolean` can equal a
+/// boolean.
+impl PartialEq<bool> for Value {
+    fn eq(&self, other: &bool) -> bool {
+        self.as_boolean() == Some(*other)
+    }
+}
+
+/// A value pinned with `Value::leak`. The value and its `MiniV8` are kept alive until this is
+/// released with `PinnedValue::release` or dropped.
+pub struct PinnedValue {
+    mv8: MiniV8,
+    handle: v8::Global<v8::Value>,
+}
+
+impl PinnedValue {
+    /// Returns the pinned value.
+    pub fn get(&self) -> Value {
+        self.mv8.scope(|scope| {
+            let value = v8::Local::new(scope, self.handle.clone());
+            Value::from_v8_value(&self.mv8, scope, value)
+        })
+    }
+
+    /// Unpins the value, returning it. The value is then kept alive only as long as any other
+    /// handles to it.
+    pub fn release(self) -> Value {
+        self.get()
+    }
+
+    /// Consumes the pin and returns an opaque pointer to it, for storing outside of Rust. The
+    /// value and its `MiniV8` stay alive until the pointer is passed to `PinnedValue::from_raw`,
+    /// and are leaked if it never is.
+    pub fn into_raw(self) -> *mut c_void {
+        Box::into_raw(Box::new(self)) as *mut c_void
+    }
+
+    /// Reclaims a pin from a pointer returned by `PinnedValue::into_raw`.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must have been returned by `PinnedValue::into_raw`, and must not be reclaimed
+    /// more than once. The returned pin must be used and dropped on the thread that created it.
+    pub unsafe fn from_raw(ptr: *mut c_void) -> PinnedValue {
+        *Box::from_raw(ptr as *mut PinnedValue)
+    }
+}
+
+impl fmt::Debug for PinnedValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PinnedValue({:?})", self.get())
+    }
+}
+
+/// The kind of a `Value`, as returned by `Value::kind`. Each variant corresponds to the `Value`
+/// variant of the same name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ValueKind {
+    Undefined,
+    Null,
+    Boolean,
+    Number,
+    BigInt,
+    Date,
+    String,
+    Symbol,
+    Array,
+    ArrayBuffer,
+    TypedArray,
+    Function,
+    Promise,
+    Map,
+    Set,
+    WeakMap,
+    WeakSet,
+    RegExp,
+    Object,
+}
+
 /// Trait for types convertible to `Value`.
 pub trait ToValue {
     /// Performs the conversion.
@@ -284,11 +925,11 @@ impl Values {
     }
 
     pub fn get(&self, index: usize) -> Value {
-        self.0.get(index).map(Clone::clone).unwrap_or(Value::Undefined)
+        self.0.get(index).cloned().unwrap_or(Value::Undefined)
     }
 
     pub fn from<T: FromValue>(&self, mv8: &MiniV8, index: usize) -> Result<T> {
-        T::from_value(self.0.get(index).map(Clone::clone).unwrap_or(Value::Undefined), mv8)
+        T::from_value(self.0.get(index).cloned().unwrap_or(Value::Undefined), mv8)
     }
 
     pub fn into<T: FromValues>(self, mv8: &MiniV8) -> Result<T> {
@@ -299,11 +940,21 @@ impl Values {
         self.0.len()
     }
 
-    pub fn iter<'a>(&'a self) -> impl Iterator<Item = &'a Value> {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Value> {
         self.0.iter()
     }
 }
 
+impl Default for Values {
+    fn default() -> Values {
+        Values::new()
+    }
+}
+
 impl FromIterator<Value> for Values {
     fn from_iter<I: IntoIterator<Item = Value>>(iter: I) -> Self {
         Values::from_vec(Vec::from_iter(iter))
@@ -324,7 +975,7 @@ impl<'a> IntoIterator for &'a Values {
     type IntoIter = slice::Iter<'a, Value>;
 
     fn into_iter(self) -> Self::IntoIter {
-        (&self.0).into_iter()
+        self.0.iter()
     }
 }
 
@@ -351,6 +1002,23 @@ pub trait FromValues: Sized {
     fn from_values(values: Values, mv8: &MiniV8) -> Result<Self>;
 }
 
+/// Wraps a number converted from JavaScript, rejecting conversions that would lose information.
+///
+/// Converting a JavaScript value to a plain Rust integer type (e.g. `u32`) already rejects such
+/// values, but converting to a plain `f32` or `f64` mirrors a Rust `as` cast, so `Infinity` and
+/// `NaN` pass through and out-of-range values silently become infinite. Converting to
+/// `Checked<T>` instead returns an error for non-finite values, for values outside the range of
+/// `T`, and (for integer types) for values with a fractional part.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Checked<T>(pub T);
+
+/// Wraps a tuple of `(key, value)` pairs, converting it to a JavaScript object with the given
+/// properties, e.g. `NamedFields((("x", 1), ("label", "origin")))` converts to
+/// `{ x: 1, label: "origin" }`. Unlike the maps that convert to objects, the values need not share
+/// a type.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NamedFields<T>(pub T);
+
 /// Wraps a variable number of `T`s.
 ///
 /// Can be used to work with variadic functions more easily. Using this type as the last argument of
@@ -375,6 +1043,12 @@ impl<T> Variadic<T> {
     }
 }
 
+impl<T> Default for Variadic<T> {
+    fn default() -> Variadic<T> {
+        Variadic::new()
+    }
+}
+
 impl<T> FromIterator<T> for Variadic<T> {
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
         Variadic(Vec::from_iter(iter))
@@ -0,0 +1,48 @@
+use crate::*;
+use std::fmt;
+
+/// A weak reference to a JavaScript value, which does not keep the value from being garbage
+/// collected. Created with `Value::downgrade`.
+///
+/// Values of the direct types (undefined, null, booleans, numbers, dates) are never collected, so
+/// weak references to them can always be upgraded.
+#[derive(Clone)]
+pub struct WeakValue(WeakInner);
+
+#[derive(Clone)]
+enum WeakInner {
+    Direct(Value),
+    Reference(MiniV8, v8::Weak<v8::Value>),
+}
+
+impl WeakValue {
+    pub(crate) fn new(mv8: &MiniV8, value: &Value) -> WeakValue {
+        mv8.scope(|scope| {
+            let value = value.to_v8_value(scope);
+            WeakValue(WeakInner::Reference(mv8.clone(), v8::Weak::new(scope, value)))
+        })
+    }
+
+    pub(crate) fn direct(value: Value) -> WeakValue {
+        WeakValue(WeakInner::Direct(value))
+    }
+
+    /// Returns the referenced value, or `None` if it has been garbage collected.
+    pub fn upgrade(&self) -> Option<Value> {
+        match self.0 {
+            WeakInner::Direct(ref value) => Some(value.clone()),
+            WeakInner::Reference(ref mv8, ref weak) => mv8.scope(|scope| {
+                weak.to_local(scope).map(|value| Value::from_v8_value(mv8, scope, value))
+            }),
+        }
+    }
+}
+
+impl fmt::Debug for WeakValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.upgrade() {
+            Some(value) => write!(f, "weak:{:?}", value),
+            None => write!(f, "weak:<collected>"),
+        }
+    }
+}
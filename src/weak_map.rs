@@ -0,0 +1,63 @@
+use crate::*;
+use std::fmt;
+
+/// Reference to a JavaScript `WeakMap`, a map whose keys are objects that it holds weakly.
+///
+/// V8 does not expose the contents of weak collections, so unlike `Map`, a `WeakMap` can't be
+/// iterated or measured. Its methods use the original built-in `WeakMap.prototype` functions
+/// captured when the `MiniV8` was created, so they are unaffected by scripts that replace them.
+#[derive(Clone)]
+pub struct WeakMap {
+    pub(crate) mv8: MiniV8,
+    pub(crate) handle: v8::Global<v8::Object>,
+}
+
+impl WeakMap {
+    /// Consumes the weak map and downgrades it to a JavaScript object.
+    pub fn into_object(self) -> Object {
+        Object { mv8: self.mv8, handle: self.handle }
+    }
+
+    /// Get the value associated with the given key. Returns `Value::Undefined` if no entry with the
+    /// key exists.
+    ///
+    /// Returns an error if `ToValue::to_value` fails for the key or if `FromValue::from_value`
+    /// fails for the value.
+    pub fn get<K: ToValue, V: FromValue>(&self, key: K) -> Result<V> {
+        let get = self.mv8.builtin(|builtins| &builtins.weak_map_get);
+        get.call_method(self.clone(), (key,))
+    }
+
+    /// Associates the given value with the given key, replacing any existing entry with the key.
+    ///
+    /// Returns an error if `ToValue::to_value` fails for either the key or the value, or an
+    /// `Error::Value` holding a JavaScript `TypeError` if the key is not an object.
+    pub fn set<K: ToValue, V: ToValue>(&self, key: K, value: V) -> Result<()> {
+        let set = self.mv8.builtin(|builtins| &builtins.weak_map_set);
+        set.call_method::<_, _, Value>(self.clone(), (key, value)).map(|_| ())
+    }
+
+    /// Removes the entry with the given key from the weak map, like JavaScript's
+    /// `WeakMap.prototype.delete`. Returns `true` if an entry was removed, `false` if no entry with
+    /// the key existed.
+    ///
+    /// Returns an error if `ToValue::to_value` fails for the key.
+    pub fn remove<K: ToValue>(&self, key: K) -> Result<bool> {
+        let delete = self.mv8.builtin(|builtins| &builtins.weak_map_delete);
+        delete.call_method(self.clone(), (key,))
+    }
+
+    /// Returns `true` if an entry with the given key exists, `false` otherwise.
+    ///
+    /// Returns an error if `ToValue::to_value` fails for the key.
+    pub fn has<K: ToValue>(&self, key: K) -> Result<bool> {
+        let has = self.mv8.builtin(|builtins| &builtins.weak_map_has);
+        has.call_method(self.clone(), (key,))
+    }
+}
+
+impl fmt::Debug for WeakMap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "WeakMap {{ <items unknown> }}")
+    }
+}
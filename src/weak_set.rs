@@ -0,0 +1,51 @@
+use crate::*;
+use std::fmt;
+
+/// Reference to a JavaScript `WeakSet`, a set of objects that it holds weakly.
+///
+/// As with `WeakMap`, the contents of a `WeakSet` can't be iterated or measured, and its methods
+/// use the original built-in `WeakSet.prototype` functions.
+#[derive(Clone)]
+pub struct WeakSet {
+    pub(crate) mv8: MiniV8,
+    pub(crate) handle: v8::Global<v8::Object>,
+}
+
+impl WeakSet {
+    /// Consumes the weak set and downgrades it to a JavaScript object.
+    pub fn into_object(self) -> Object {
+        Object { mv8: self.mv8, handle: self.handle }
+    }
+
+    /// Adds the given value to the weak set. This does nothing if the value is already in the set.
+    ///
+    /// Returns an error if `ToValue::to_value` fails for the value, or an `Error::Value` holding a
+    /// JavaScript `TypeError` if the value is not an object.
+    pub fn add<V: ToValue>(&self, value: V) -> Result<()> {
+        let add = self.mv8.builtin(|builtins| &builtins.weak_set_add);
+        add.call_method::<_, _, Value>(self.clone(), (value,)).map(|_| ())
+    }
+
+    /// Removes the given value from the weak set, like JavaScript's `WeakSet.prototype.delete`.
+    /// Returns `true` if the value was removed, `false` if it was not in the set.
+    ///
+    /// Returns an error if `ToValue::to_value` fails for the value.
+    pub fn remove<V: ToValue>(&self, value: V) -> Result<bool> {
+        let delete = self.mv8.builtin(|builtins| &builtins.weak_set_delete);
+        delete.call_method(self.clone(), (value,))
+    }
+
+    /// Returns `true` if the given value is in the weak set, `false` otherwise.
+    ///
+    /// Returns an error if `ToValue::to_value` fails for the value.
+    pub fn has<V: ToValue>(&self, value: V) -> Result<bool> {
+        let has = self.mv8.builtin(|builtins| &builtins.weak_set_has);
+        has.call_method(self.clone(), (value,))
+    }
+}
+
+impl fmt::Debug for WeakSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "WeakSet {{ <items unknown> }}")
+    }
+}